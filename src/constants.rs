@@ -1,10 +1,26 @@
 /// The current version of the package.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// The characters that indicate the beginning of an Osmia statement.
+/// The default characters that indicate the beginning of an Osmia statement.
 pub const START_DELIMITER: &str = "{{";
-/// The characters that indicate the end of an Osmia statement.
+/// The default characters that indicate the end of an Osmia statement.
 pub const END_DELIMITER: &str = "}}";
+/// The default characters that, after the start delimiter, indicate a comment.
+pub const COMMENT_MARKER: &str = "#";
 
 /// The location inside the context where the methods are stored.
 pub const METHOD_CTX_LOCATION: &str = "_method";
+
+/// The path under which [Osmia::render_files](crate::Osmia::render_files) stores
+/// the output that is not inside a file block.
+pub const MAIN_OUTPUT: &str = "";
+
+/// The variable with the position of the innermost `for` loop.
+pub const LOOP_VARIABLE: &str = "loop";
+
+/// The largest number of items of a range that can be serialized, as every
+/// item is stored in memory.
+pub const MAX_SERIALIZED_RANGE: usize = 1 << 20;
+
+/// The method that prints a value without escaping it.
+pub const SAFE_METHOD: &str = "safe";
@@ -26,6 +26,10 @@ mod model;
 mod utils;
 mod types;
 mod stdlib;
+mod template;
+
+use std::rc::Rc;
+use std::collections::BTreeMap;
 
 #[cfg(test)]
 use macro_test::macro_tests;
@@ -37,14 +41,39 @@ use types::{
 	LexerCode,
 	ParserCode,
 	OsmiaOutput,
+};
+use model::ctx;
+use constants::METHOD_CTX_LOCATION;
+pub use constants::{MAIN_OUTPUT, MAX_SERIALIZED_RANGE};
 
+pub use model::error::{
 	OsmiaError,
+	Diagnostic,
+	ErrorInfo,
+	Location,
+	LimitKind,
 };
-use model::ctx;
+pub use model::loader::{
+	TemplateLoader,
+	FsLoader,
+	MapLoader,
+};
+use model::loader::TemplateResolver;
+pub use template::Template;
+pub use model::interpreter::callable::NativeFn;
+pub use model::interpreter::{
+	MethodType,
+	Limits,
+	EscapeMode,
+	PrintSink,
+};
+pub use model::lexer::Syntax;
+pub use types::Ctx;
 
 #[cfg(feature = "dumper")]
+use model::ctx::CtxJsonDumper;
 use model::ctx::{
-	CtxJsonDumper,
+	CtxValue,
 	JsonTreeKey,
 };
 use model::lexer::{
@@ -89,18 +118,327 @@ pub trait CodeInterpreter: for<'a> TryFrom<&'a str> {
 /// Default osmia template engine API.
 pub struct Osmia {
 	ctx: types::Ctx,
+	loader: Option<Rc<dyn TemplateLoader>>,
+	limits: Limits,
+	escape: EscapeMode,
+	syntax: Syntax,
+	print_sink: PrintSink,
 }
 
 impl Osmia {
 	fn new(ctx: types::Ctx) -> Self {
 		Self {
 			ctx,
+			loader: None,
+			limits: Limits::default(),
+			escape: EscapeMode::default(),
+			syntax: Syntax::default(),
+			print_sink: PrintSink::default(),
 		}
 	}
 
+	/// Sets the [TemplateLoader] used to resolve the templates referenced by the code.
+	pub fn with_loader(mut self, loader: impl TemplateLoader + 'static) -> Self {
+		self.set_loader(loader);
+		self
+	}
+
+	pub fn set_loader(&mut self, loader: impl TemplateLoader + 'static) {
+		self.loader = Some(Rc::new(loader));
+	}
+
+	/// Sets the [Limits] enforced while running the code.
+	pub fn with_limits(mut self, limits: Limits) -> Self {
+		self.set_limits(limits);
+		self
+	}
+
+	pub fn set_limits(&mut self, limits: Limits) {
+		self.limits = limits;
+	}
+
+	/// Sets the [EscapeMode] applied to the printed values.
+	pub fn with_escape(mut self, escape: EscapeMode) -> Self {
+		self.set_escape(escape);
+		self
+	}
+
+	pub fn set_escape(&mut self, escape: EscapeMode) {
+		self.escape = escape;
+	}
+
+	/// Sets the [Syntax] that delimits the statements of the code.
+	pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+		self.set_syntax(syntax);
+		self
+	}
+
+	pub fn set_syntax(&mut self, syntax: Syntax) {
+		self.syntax = syntax;
+	}
+
+	/// Sets the [PrintSink] where `{{print value}}` sends the values.
+	pub fn with_print_sink(mut self, print_sink: PrintSink) -> Self {
+		self.set_print_sink(print_sink);
+		self
+	}
+
+	pub fn set_print_sink(&mut self, print_sink: PrintSink) {
+		self.print_sink = print_sink;
+	}
+
+	/// Creates an [Osmia] instance whose context is built from any serializable value.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	/// use serde::Serialize;
+	///
+	/// #[derive(Serialize)]
+	/// struct User { name: String, age: u32 }
+	///
+	/// let user = User { name: "Marvin".into(), age: 42 };
+	/// let mut osmia = Osmia::from_serialize(&user).unwrap();
+	/// assert_eq!(osmia.run_code("{{ name }}: {{ age }}").unwrap(), "Marvin: 42");
+	/// ```
+	pub fn from_serialize<T: serde::Serialize + ?Sized>(data: &T) -> Result<Self, OsmiaError> {
+		Ok(Self::new(types::Ctx::from_serialize(data)?))
+	}
+
+	/// Stores any serializable value in the context under the given key.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	///
+	/// let mut osmia = Osmia::default();
+	/// osmia.set("user", &serde_json::json!({ "name": "Marvin" })).unwrap();
+	/// osmia.set("user.langs", &vec!["rust", "c"]).unwrap();
+	/// assert_eq!(osmia.run_code("{{ user.name }}: {{ user.langs[0] }}").unwrap(), "Marvin: rust");
+	/// ```
+	pub fn set<T: serde::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), OsmiaError> {
+		let key = JsonTreeKey::try_parse(key)?;
+		let value = types::Ctx::serialize_value(value)?;
+		self.ctx.set(&key, value)
+	}
+
+	/// Reads a variable of the context into any deserializable value.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	///
+	/// let mut osmia = Osmia::try_from(r#"{ "prices": [10.5, 20] }"#).unwrap();
+	/// osmia.run_code("{{ total = prices[0] + prices[1] }}").unwrap();
+	/// assert_eq!(osmia.get::<f64>("total").unwrap(), 30.5);
+	/// assert_eq!(osmia.get::<Vec<f64>>("prices").unwrap(), vec![10.5, 20.0]);
+	/// assert!(osmia.get::<String>("total").is_err());
+	/// assert!(osmia.get::<f64>("discount").is_err());
+	/// ```
+	pub fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, OsmiaError> {
+		self.ctx.get_as(&JsonTreeKey::try_parse(key)?)
+	}
+
+	/// Registers a Rust closure as a function callable from the templates.
+	///
+	/// The function accepts any number of arguments. Nested keys create the
+	/// objects holding the function when they do not exist.
+	/// See [NativeFn] to declare the parameters of the function.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	/// use serde_json::json;
+	///
+	/// let currency = String::from("EUR");
+	/// let mut osmia = Osmia::default();
+	/// osmia.register_fn("app.format_money", move |args| {
+	///   let amount = args[0].as_f64().ok_or("Expected a number")?;
+	///   Ok(json!(format!("{:.2} {}", amount, currency)))
+	/// }).unwrap();
+	/// assert_eq!(osmia.run_code("{{ app.format_money(12.5) }}").unwrap(), "12.50 EUR");
+	/// ```
+	pub fn register_fn(
+		&mut self,
+		key: &str,
+		call: impl Fn(&[serde_json::Value]) -> Result<serde_json::Value, OsmiaError> + Send + Sync + 'static
+	) -> Result<(), OsmiaError> {
+		self.register_native_fn(key, NativeFn::new(call))
+	}
+
+	/// Registers a [NativeFn] as a function callable from the templates.
+	pub fn register_native_fn(&mut self, key: &str, call: impl Into<NativeFn>) -> Result<(), OsmiaError> {
+		let key = JsonTreeKey::try_parse(key)?;
+		let callable = call.into().into_callable()?;
+		self.ctx.set_creating_parents(&key, CtxValue::Callable(callable).into())
+	}
+
+	/// Registers a Rust closure as a method callable with `value?name()`.
+	///
+	/// The value the method is called on is received as the first argument.
+	/// Fails if the method already exists, see [Osmia::override_method].
+	///
+	/// ```rust
+	/// use osmia::{Osmia, MethodType};
+	/// use serde_json::json;
+	///
+	/// let mut osmia = Osmia::default();
+	/// osmia.register_method(MethodType::Str, "slugify", |args| {
+	///   let s = args[0].as_str().ok_or("Expected a string")?;
+	///   Ok(json!(s.to_lowercase().split_whitespace().collect::<Vec<_>>().join("-")))
+	/// }).unwrap();
+	/// assert_eq!(osmia.run_code(r#"{{ "Hello World"?slugify() }}"#).unwrap(), "hello-world");
+	/// assert!(osmia.register_method(MethodType::Str, "len", |_| Ok(json!(0))).is_err());
+	/// ```
+	pub fn register_method(
+		&mut self,
+		method_type: MethodType,
+		name: &str,
+		call: impl Fn(&[serde_json::Value]) -> Result<serde_json::Value, OsmiaError> + Send + Sync + 'static
+	) -> Result<(), OsmiaError> {
+		self.add_method(method_type, name, NativeFn::new(call), false)
+	}
+
+	/// Registers a [NativeFn] as a method callable with `value?name()`.
+	///
+	/// The value the method is called on is received as the first argument.
+	/// Fails if the method already exists, see [Osmia::override_method].
+	pub fn register_native_method(
+		&mut self,
+		method_type: MethodType,
+		name: &str,
+		call: impl Into<NativeFn>
+	) -> Result<(), OsmiaError> {
+		self.add_method(method_type, name, call.into(), false)
+	}
+
+	/// Registers a [NativeFn] as a method, replacing the method if it already exists.
+	///
+	/// ```rust
+	/// use osmia::{Osmia, MethodType, NativeFn};
+	/// use serde_json::json;
+	///
+	/// let mut osmia = Osmia::default();
+	/// osmia.override_method(MethodType::Any, "to_string", NativeFn::new(|_| Ok(json!("***")))).unwrap();
+	/// assert_eq!(osmia.run_code("{{ 42?to_string() }}").unwrap(), "***");
+	/// ```
+	pub fn override_method(
+		&mut self,
+		method_type: MethodType,
+		name: &str,
+		call: impl Into<NativeFn>
+	) -> Result<(), OsmiaError> {
+		self.add_method(method_type, name, call.into(), true)
+	}
+
+	fn add_method(
+		&mut self,
+		method_type: MethodType,
+		name: &str,
+		call: NativeFn,
+		allow_shadowing: bool
+	) -> Result<(), OsmiaError> {
+		let keys: Vec<Vec<JsonTreeKey<String>>> = method_type.expressions().iter()
+			.map(|t| vec![
+				JsonTreeKey::Key(METHOD_CTX_LOCATION.into()),
+				JsonTreeKey::Key(t.into()),
+				JsonTreeKey::Key(name.into()),
+			])
+			.collect();
+		if !allow_shadowing {
+			if let Some(key) = keys.iter().find(|key| self.ctx.get(key).is_ok()) {
+				return Err(OsmiaError::ctx(
+					format!("The method {} already exists", name),
+					JsonTreeKey::path(key)
+				));
+			}
+		}
+		let callable = call.into_callable()?;
+		for key in keys {
+			self.ctx.set(&key, CtxValue::Callable(callable.clone()).into())?;
+		}
+		Ok(())
+	}
+
 	pub fn run_code(&mut self, code: &str) -> Result<OsmiaOutput, OsmiaError> {
 		self.run(code)
 	}
+
+	/// Runs the code returning the content of every file block by path, with
+	/// the rest of the output under [MAIN_OUTPUT].
+	pub fn render_files(&mut self, code: &str) -> Result<BTreeMap<String, String>, OsmiaError> {
+		self.compile(code)?.render_files(&mut self.ctx)
+	}
+
+	/// Runs the code returning, along with the output, the values printed
+	/// when using [PrintSink::Buffer].
+	pub fn run_code_with_prints(&mut self, code: &str) -> Result<(OsmiaOutput, Vec<String>), OsmiaError> {
+		self.compile(code)?.render_with_prints(&mut self.ctx)
+	}
+
+	/// Runs the code writing the output into `writer` as it is generated.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	///
+	/// let mut osmia = Osmia::try_from(r#"{ "name": "Marvin" }"#).unwrap();
+	/// let mut output: Vec<u8> = Vec::new();
+	/// osmia.render_to("Hello {{ name }}!", &mut output).unwrap();
+	/// assert_eq!(String::from_utf8(output).unwrap(), "Hello Marvin!");
+	/// ```
+	pub fn render_to(&mut self, code: &str, writer: &mut impl std::io::Write) -> Result<(), OsmiaError> {
+		self.compile(code)?.render_to(&mut self.ctx, writer)
+	}
+
+	/// Same as [Osmia::render_to], returning the values printed when using
+	/// [PrintSink::Buffer].
+	pub fn render_to_with_prints(
+		&mut self,
+		code: &str,
+		writer: &mut impl std::io::Write
+	) -> Result<Vec<String>, OsmiaError> {
+		self.compile(code)?.render_to_with_prints(&mut self.ctx, writer)
+	}
+
+	/// Looks for syntax errors in the code without running it.
+	///
+	/// The parser recovers at the end of each statement, so every syntax error
+	/// is reported at once instead of only the first one. Included and extended
+	/// templates are not loaded.
+	///
+	/// ```rust
+	/// use osmia::Osmia;
+	///
+	/// let osmia = Osmia::default();
+	/// assert!(osmia.check("Hello {{ name }}!").is_empty());
+	/// let diagnostics = osmia.check("{{if}}a{{fi}}\n{{ 1 + }}");
+	/// assert_eq!(diagnostics.len(), 2);
+	/// ```
+	pub fn check(&self, code: &str) -> Vec<Diagnostic> {
+		let errors = match OsmiaLexer::new().with_syntax(self.syntax.clone()).lex(code) {
+			Err(error) => vec![error],
+			Ok(lexed) => OsmiaParser::new().parse_all(lexed).err().unwrap_or_default(),
+		};
+		errors.into_iter()
+			.map(|error| error.with_code_trace(code).into())
+			.collect()
+	}
+
+	/// Lexes, parses and resolves the code into a [Template] that can be rendered many times.
+	pub fn compile(&self, code: &str) -> Result<Template, OsmiaError> {
+		let lexed = OsmiaLexer::new().with_syntax(self.syntax.clone()).lex(code)?;
+		let parsed = Self::parse(lexed)
+			.and_then(|parsed| TemplateResolver::new(self.loader.as_deref())
+				.with_syntax(self.syntax.clone())
+				.resolve(parsed)
+			)
+			.map_err(|e| e.with_code_trace(code))?;
+		Ok(Template::new(
+			code,
+			parsed,
+			self.loader.clone(),
+			self.limits,
+			self.escape.clone(),
+			self.syntax.clone(),
+			self.print_sink.clone(),
+		))
+	}
 }
 
 #[cfg(feature = "dumper")]
@@ -146,9 +484,7 @@ impl CodeInterpreter for Osmia {
 	}
 
 	fn run(&mut self, code: &str) -> Result<Self::Output, Self::InterpreterError> {
-		let lexed = Self::lex(code)?;
-		let parsed = Self::parse(lexed)?;
-		Self::interpret(&mut self.ctx, parsed)
+		self.compile(code)?.render(&mut self.ctx)
 	}
 }
 
@@ -159,3 +495,11 @@ impl TryFrom<&str> for Osmia {
 		Ok(Self::new(types::Ctx::try_from(ctx)?))
 	}
 }
+
+impl TryFrom<serde_json::Value> for Osmia {
+	type Error = OsmiaError;
+
+	fn try_from(ctx: serde_json::Value) -> Result<Self, Self::Error> {
+		Ok(Self::new(types::Ctx::try_from(ctx)?))
+	}
+}
@@ -1,4 +1,6 @@
 use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 
 use super::*;
 use crate::stdlib;
@@ -40,18 +42,38 @@ impl Ctx {
 		&self,
 		key: &Vec<JsonTreeKey<String>>
 	) -> Result<&JsonTree<String, CtxValue>, OsmiaError> {
+		self.lookup(key).map_err(|e| e.format_get_error(key))
+	}
+
+	/// Same as [Ctx::get], but a key that does not exist results in `None`
+	/// instead of an error.
+	pub fn get_optional(
+		&self,
+		key: &[JsonTreeKey<String>]
+	) -> Result<Option<&JsonTree<String, CtxValue>>, OsmiaError> {
+		match self.lookup(key) {
+			Ok(v) => Ok(Some(v)),
+			Err(JsonTreeError::KeyNotFound(_)) => Ok(None),
+			Err(e) => Err(e.format_get_error(key)),
+		}
+	}
+
+	fn lookup(
+		&self,
+		key: &[JsonTreeKey<String>]
+	) -> Result<&JsonTree<String, CtxValue>, JsonTreeError<JsonTreeKey<String>>> {
 		let mut error: Option<JsonTreeError<JsonTreeKey<String>>> = None;
 		for scope in self.ctx.iter().rev() {
 			error = match scope.get(&mut key.iter()) {
 				Ok(v) => return Ok(v),
 				Err(e) => match e {
 					JsonTreeError::KeyNotFound(_) => Some(e),
-					e => return Err(e.format_get_error()),
+					e => return Err(e),
 				}
 			};
 		}
 		match error {
-			Some(e) => Err(e.format_get_error()),
+			Some(e) => Err(e),
 			None => unreachable!(),
 		}
 	}
@@ -65,8 +87,8 @@ impl Ctx {
 		match current_scope.set(&mut key.iter(), value) {
 			Ok(_) => Ok(()),
 			Err(e) => match e {
-				JsonTreeError::KeyNotFound(_) => Err(e.format_set_error()),
-				e => Err(e.format_set_error()),
+				JsonTreeError::KeyNotFound(_) => Err(e.format_set_error(key)),
+				e => Err(e.format_set_error(key)),
 			}
 		}
 	}
@@ -80,37 +102,106 @@ impl Ctx {
 		for scope in self.ctx.iter_mut().rev() {
 			let get_result = scope.get(&mut root_variable.iter());
 			match get_result {
-				Ok(_) => return scope.set(&mut key.iter(), value).map_err(|e| e.format_set_error()),
+				Ok(_) => return scope.set(&mut key.iter(), value).map_err(|e| e.format_set_error(key)),
 				Err(e) => match e {
 					JsonTreeError::KeyNotFound(_) => (),
-					e => return Err(e.format_set_error()),
+					e => return Err(e.format_set_error(key)),
 				}
 			}
 		}
 		self.set_in_current_scope(key, value)
 	}
 
+	/// Sets the value, creating the missing objects of the key as empty objects.
+	pub fn set_creating_parents(
+		&mut self,
+		key: &Vec<JsonTreeKey<String>>,
+		value: JsonTree<String, CtxValue>
+	) -> Result<(), OsmiaError> {
+		for i in 1..key.len() {
+			let parent = key[..i].to_vec();
+			if let (Err(_), JsonTreeKey::Key(_)) = (self.get(&parent), &key[i - 1]) {
+				self.set(&parent, JsonTree::new_obj())?;
+			}
+		}
+		self.set(key, value)
+	}
+
 	pub fn raw(&self) -> &VecDeque<JsonTree<String, CtxValue>> {
 		&self.ctx
 	}
 }
 
+impl Ctx {
+	/// Builds a context from any serializable value.
+	///
+	/// The value must serialize into an object or an array (stored as `ctx`).
+	pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, OsmiaError> {
+		Self::try_from(Self::serialize_value(value)?)
+	}
+
+	/// Converts any serializable value into a node of the context.
+	pub fn serialize_value<T: Serialize + ?Sized>(
+		value: &T
+	) -> Result<JsonTree<String, CtxValue>, OsmiaError> {
+		match serde_json::to_value(value) {
+			Ok(value) => Self::json_value(value),
+			Err(e) => Err(format!("Invalid value: {}", e).into()),
+		}
+	}
+
+	/// Reads the node under the key and converts it into any deserializable value.
+	pub fn get_as<T: DeserializeOwned>(
+		&self,
+		key: &Vec<JsonTreeKey<String>>
+	) -> Result<T, OsmiaError> {
+		let node = self.get(key)?;
+		serde_json::to_value(node)
+			.and_then(T::deserialize)
+			.map_err(|e| OsmiaError::conversion(
+				format!("Invalid type: {}", e),
+				JsonTreeKey::path(key)
+			))
+	}
+
+	fn json_value(value: serde_json::Value) -> Result<JsonTree<String, CtxValue>, OsmiaError> {
+		JsonTree::deserialize(value)
+			.map_err(|e| format!("Invalid value: {}", e).into())
+	}
+}
+
 impl<'a> TryFrom<&'a str> for Ctx {
 	type Error = OsmiaError;
 
 	fn try_from(json: &'a str) -> Result<Self, Self::Error> {
-		let content: JsonTree<String, CtxValue> = match serde_json::from_str(json) {
-			Ok(c) => match c {
-				JsonTree::Object(_) => c,
-				JsonTree::Array(_) => {
-					let mut obj = JsonTree::new_obj();
-					let key = JsonTreeKey::try_parse("ctx").unwrap();
-					obj.set(&mut key.iter(), c).unwrap();
-					obj
-				},
-				_ => return Err("Ctx must be an object".into()),
-			}
-			Err(e) => return Err(format!("Invalid JSON: {}", e)),
+		match serde_json::from_str::<JsonTree<String, CtxValue>>(json) {
+			Ok(content) => Self::try_from(content),
+			Err(e) => Err(format!("Invalid JSON: {}", e).into()),
+		}
+	}
+}
+
+impl TryFrom<serde_json::Value> for Ctx {
+	type Error = OsmiaError;
+
+	fn try_from(json: serde_json::Value) -> Result<Self, Self::Error> {
+		Self::try_from(Self::json_value(json)?)
+	}
+}
+
+impl TryFrom<JsonTree<String, CtxValue>> for Ctx {
+	type Error = OsmiaError;
+
+	fn try_from(content: JsonTree<String, CtxValue>) -> Result<Self, Self::Error> {
+		let content = match content {
+			JsonTree::Object(_) => content,
+			JsonTree::Array(_) => {
+				let mut obj = JsonTree::new_obj();
+				let key = JsonTreeKey::try_parse("ctx").unwrap();
+				obj.set(&mut key.iter(), content).unwrap();
+				obj
+			},
+			_ => return Err("Ctx must be an object".into()),
 		};
 		let mut ctx = Self::from(content);
 		Self::default_libs(&mut ctx);
@@ -119,10 +210,9 @@ impl<'a> TryFrom<&'a str> for Ctx {
 	}
 }
 
-
 impl JsonTreeError<JsonTreeKey<String>> {
-	fn format_get_error(self) -> OsmiaError {
-		match self {
+	fn format_get_error(self, key: &[JsonTreeKey<String>]) -> OsmiaError {
+		let msg = match self {
 			JsonTreeError::AccessValue(k) => format!("Cannot access a value: {}", k),
 			JsonTreeError::ArrayOutOfBounds((idx, len)) => format!(
 				"Array index out of bounds. Attempted to access index {} in an array of length {}",
@@ -132,11 +222,12 @@ impl JsonTreeError<JsonTreeKey<String>> {
 			JsonTreeError::KeyInArray => format!("Cannot get by key from an array"),
 			JsonTreeError::KeyNotFound(k) => format!("{} not found", k),
 			JsonTreeError::NoKey => unreachable!(),
-		}
+		};
+		OsmiaError::ctx(msg, JsonTreeKey::path(key))
 	}
 
-	fn format_set_error(self) -> OsmiaError {
-		match self {
+	fn format_set_error(self, key: &[JsonTreeKey<String>]) -> OsmiaError {
+		let msg = match self {
 			JsonTreeError::AccessValue(k) => format!("Cannot access a value: {}", k),
 			JsonTreeError::ArrayOutOfBounds((idx, len)) => format!(
 				"Array index out of bounds. Attempted to access index {} in an array of length {}",
@@ -146,6 +237,7 @@ impl JsonTreeError<JsonTreeKey<String>> {
 			JsonTreeError::KeyInArray => format!("Cannot set by key from an array"),
 			JsonTreeError::KeyNotFound(k) => format!("{} not found", k),
 			JsonTreeError::NoKey => unreachable!(),
-		}
+		};
+		OsmiaError::ctx(msg, JsonTreeKey::path(key))
 	}
 }
@@ -54,6 +54,7 @@ impl Serialize for DumpNode {
 					CtxValue::Str(e) => state.serialize_field("value", e)?,
 					CtxValue::Bool(e) => state.serialize_field("value", e)?,
 					CtxValue::Null => state.serialize_field("value", &())?,
+					CtxValue::Range(r) => state.serialize_field("value", &r.to_string())?,
 				};
 			},
 			DumpNode::Node(v) => {
@@ -1,8 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::model::interpreter::Callable;
+use crate::model::expr::Range;
 use crate::ctx::JsonTree;
 
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum CtxValue {
 	Int(i64),
@@ -10,6 +11,8 @@ pub enum CtxValue {
 	Str(String),
 	Bool(bool),
 	Null,
+	#[serde(skip_deserializing)]
+	Range(Range),
 	#[serde(skip)]
 	Callable(Callable),
 }
@@ -2,7 +2,7 @@ use std::hash::Hash;
 use std::collections::HashMap;
 use std::iter::Peekable;
 use std::fmt::Display;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use super::{
 	JsonTreeKey,
 	JsonTreeError,
@@ -11,7 +11,7 @@ use super::{
 pub trait JsonTreeKeyTrait: Eq + Hash + Clone + Display {}
 impl<K> JsonTreeKeyTrait for K where K: Eq + Hash + Clone + Display {}
 
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum JsonTree<K: JsonTreeKeyTrait, T> {
 	Value(T),
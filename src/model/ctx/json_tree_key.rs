@@ -3,6 +3,7 @@ use crate::model::lexer::{
 	OsmiaLexerScanner,
 	Token
 };
+use crate::types::OsmiaError;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum JsonTreeKey<K: Clone + Display> {
@@ -11,12 +12,13 @@ pub enum JsonTreeKey<K: Clone + Display> {
 }
 
 impl JsonTreeKey<String> {
-	pub fn try_parse(k: &str) -> Result<Vec<Self>, String> {
-		let var_iter = OsmiaLexerScanner::new(k).scan_stmt()?;
+	pub fn try_parse(k: &str) -> Result<Vec<Self>, OsmiaError> {
+		let var_iter = OsmiaLexerScanner::new(k).scan_stmt()
+			.map_err(|e| OsmiaError::ctx(e.message(), k))?;
 		if var_iter.len() == 0 {
-			return Err("Empty".into());
+			return Err(OsmiaError::ctx("Empty", k));
 		}
-		let mut var_iter = var_iter.into_iter();
+		let mut var_iter = var_iter.into_iter().map(Token::from);
 		let mut keys = Vec::new();
 		let mut first = true;
 		while let Some(token) = var_iter.next() {
@@ -24,7 +26,7 @@ impl JsonTreeKey<String> {
 				match token {
 					Token::Alpha(_) => (),
 					Token::Null => (),
-					_ => return Err("Invalid key".into())
+					_ => return Err(OsmiaError::ctx("Invalid key", k))
 				}
 				first = false;
 			}
@@ -36,7 +38,7 @@ impl JsonTreeKey<String> {
 						keys.push(JsonTreeKey::Key(a.into()));
 					}
 					else {
-						return Err("Invalid dot '.' in key".into());
+						return Err(OsmiaError::ctx("Invalid dot '.' in key", k));
 					}
 				},
 				Token::ArrayStart => {
@@ -44,26 +46,43 @@ impl JsonTreeKey<String> {
 						Some(Token::Number(n)) => {
 							let n = match n.parse::<usize>() {
 								Ok(n) => n,
-								Err(_) => return Err("Expected positive integer".into()),
+								Err(_) => return Err(OsmiaError::ctx("Expected positive integer", k)),
 							};
 							keys.push(JsonTreeKey::Index(n));
 						},
-						None => return Err("Unclosed array".into()),
-						_ => return Err("Expected index number".into())
+						None => return Err(OsmiaError::ctx("Unclosed array", k)),
+						_ => return Err(OsmiaError::ctx("Expected index number", k))
 					}
 					match var_iter.next() {
 						Some(Token::ArrayEnd) => (),
-						_ => return Err("Unclosed array".into())
+						_ => return Err(OsmiaError::ctx("Unclosed array", k))
 					}
 				},
 				#[cfg(debug_assertions)]
-				t => return Err(format!("Invalid token in key: {:?}", t).into()),
+				t => return Err(OsmiaError::ctx(format!("Invalid token in key: {:?}", t), k)),
 				#[cfg(not(debug_assertions))]
-				t => return Err("Invalid token in key".into())
+				t => return Err(OsmiaError::ctx("Invalid token in key", k))
 			}
 		}
 		Ok(keys)
 	}
+
+	/// Renders the keys as they would be written in the code: `arr[0].name`.
+	pub fn path(keys: &[Self]) -> String {
+		let mut path = String::new();
+		for key in keys {
+			match key {
+				JsonTreeKey::Index(i) => path.push_str(&format!("[{}]", i)),
+				JsonTreeKey::Key(k) => {
+					if !path.is_empty() {
+						path.push('.');
+					}
+					path.push_str(k);
+				},
+			}
+		}
+		path
+	}
 }
 
 impl<K: Clone + Display> Display for JsonTreeKey<K> {
@@ -0,0 +1,62 @@
+use std::fmt::Display;
+use super::{
+	ErrorInfo,
+	Location,
+	OsmiaError,
+};
+
+/// Problem found in the code by [Osmia::check](crate::Osmia::check).
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let osmia = Osmia::default();
+/// let diagnostics = osmia.check("{{ 1 + }}\n{{for 1 in arr}}{{ i }}{{done}}");
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!(diagnostics[0].location().unwrap().line(), 1);
+/// assert_eq!(diagnostics[1].location().unwrap().line(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+	error: OsmiaError,
+}
+
+impl Diagnostic {
+	pub fn new(error: OsmiaError) -> Self {
+		Self { error }
+	}
+
+	pub fn error(&self) -> &OsmiaError {
+		&self.error
+	}
+
+	pub fn info(&self) -> &ErrorInfo {
+		self.error.info()
+	}
+
+	pub fn message(&self) -> &str {
+		self.error.message()
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.error.location()
+	}
+}
+
+impl From<OsmiaError> for Diagnostic {
+	fn from(error: OsmiaError) -> Self {
+		Self::new(error)
+	}
+}
+
+impl From<Diagnostic> for OsmiaError {
+	fn from(diagnostic: Diagnostic) -> Self {
+		diagnostic.error
+	}
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.error)
+	}
+}
@@ -0,0 +1,67 @@
+use super::Location;
+use crate::utils::code_trace;
+
+/// Details shared by all the [OsmiaError](super::OsmiaError) variants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorInfo {
+	msg: String,
+	location: Option<Location>,
+	culprit: Option<String>,
+	trace: Option<String>,
+}
+
+impl ErrorInfo {
+	pub fn new(msg: impl Into<String>) -> Self {
+		Self {
+			msg: msg.into(),
+			location: None,
+			culprit: None,
+			trace: None,
+		}
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = Some(location);
+		self
+	}
+
+	/// Stores the offending element: the token for lexer and parser errors,
+	/// the key path for context errors.
+	pub fn with_culprit(mut self, culprit: impl Into<String>) -> Self {
+		self.culprit = Some(culprit.into());
+		self
+	}
+
+	/// Stores the location only if the error does not have one yet.
+	pub fn or_location(mut self, location: Option<Location>) -> Self {
+		if self.location.is_none() {
+			self.location = location;
+		}
+		self
+	}
+
+	/// Stores a snippet of the given code pointing to the location of the error.
+	/// Nothing is stored if the location is unknown or if there is already a trace.
+	pub fn with_code_trace(mut self, code: &str) -> Self {
+		if let (Some(location), None) = (&self.location, &self.trace) {
+			self.trace = Some(code_trace(code, location.offset().min(code.len()), ""));
+		}
+		self
+	}
+
+	pub fn message(&self) -> &str {
+		&self.msg
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location
+	}
+
+	pub fn culprit(&self) -> Option<&str> {
+		self.culprit.as_deref()
+	}
+
+	pub fn trace(&self) -> Option<&str> {
+		self.trace.as_deref()
+	}
+}
@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+/// Execution limit that stopped the interpretation of the code.
+///
+/// See [Limits](crate::Limits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+	/// Too many statements and expressions were evaluated.
+	Fuel,
+	/// Too many nested function calls.
+	CallDepth,
+	/// The output grew bigger than allowed.
+	Output,
+	/// An array or object grew bigger than allowed.
+	CollectionSize,
+}
+
+impl LimitKind {
+	pub(crate) fn message(&self, max: usize) -> String {
+		match self {
+			LimitKind::Fuel => format!("Fuel exhausted: more than {} steps evaluated", max),
+			LimitKind::CallDepth => format!("Maximum call depth of {} exceeded", max),
+			LimitKind::Output => format!("Output exceeds the maximum of {} bytes", max),
+			LimitKind::CollectionSize => format!("Collection exceeds the maximum of {} elements", max),
+		}
+	}
+}
+
+impl Display for LimitKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			LimitKind::Fuel => write!(f, "fuel"),
+			LimitKind::CallDepth => write!(f, "call depth"),
+			LimitKind::Output => write!(f, "output"),
+			LimitKind::CollectionSize => write!(f, "collection size"),
+		}
+	}
+}
@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+/// Position of a character inside the code.
+///
+/// The offset is the byte index in the code, while the line and the column
+/// start counting at 1, like most editors do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+	offset: usize,
+	line: usize,
+	column: usize,
+}
+
+impl Location {
+	pub fn new(offset: usize, line: usize, column: usize) -> Self {
+		Self { offset, line, column }
+	}
+
+	/// Computes the line and column of the given byte offset.
+	pub fn from_offset(code: &str, offset: usize) -> Self {
+		let offset = offset.min(code.len());
+		let before = &code.as_bytes()[..offset];
+		let line = before.iter().filter(|c| **c == b'\n').count() + 1;
+		let column = match before.iter().rposition(|c| *c == b'\n') {
+			Some(i) => offset - i,
+			None => offset + 1,
+		};
+		Self::new(offset, line, column)
+	}
+
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	pub fn column(&self) -> usize {
+		self.column
+	}
+}
+
+impl Display for Location {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "line {}, column {}", self.line, self.column)
+	}
+}
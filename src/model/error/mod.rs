@@ -0,0 +1,13 @@
+mod diagnostic;
+mod error_info;
+mod limit_kind;
+mod location;
+mod node_location;
+mod osmia_error;
+
+pub use diagnostic::Diagnostic;
+pub use error_info::ErrorInfo;
+pub use limit_kind::LimitKind;
+pub use location::Location;
+pub use node_location::NodeLocation;
+pub use osmia_error::OsmiaError;
@@ -0,0 +1,26 @@
+use super::Location;
+
+/// Location of a node of the syntax tree in the code.
+///
+/// Nodes built by hand have no location. The location does not take part
+/// in comparisons: two nodes are equal no matter where they were written.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NodeLocation(Option<Location>);
+
+impl NodeLocation {
+	pub fn get(&self) -> Option<Location> {
+		self.0
+	}
+}
+
+impl From<Location> for NodeLocation {
+	fn from(location: Location) -> Self {
+		Self(Some(location))
+	}
+}
+
+impl PartialEq for NodeLocation {
+	fn eq(&self, _: &Self) -> bool {
+		true
+	}
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+use super::{
+	ErrorInfo,
+	LimitKind,
+	Location,
+};
+
+/// Error produced while lexing, parsing or interpreting osmia code.
+///
+/// The variant tells in which step the code failed, while the [ErrorInfo]
+/// holds the message and, when known, the location of the error.
+///
+/// ```rust
+/// use osmia::{Osmia, OsmiaError};
+///
+/// let mut osmia = Osmia::default();
+/// match osmia.run_code("{{ user.name }}").unwrap_err() {
+///   OsmiaError::Ctx(info) => assert_eq!(info.culprit(), Some("user.name")),
+///   e => panic!("Unexpected error: {}", e),
+/// }
+/// match osmia.run_code("Hello\n{{ @ }}").unwrap_err() {
+///   OsmiaError::Lexer(info) => {
+///     let location = info.location().unwrap();
+///     assert_eq!((location.line(), location.column()), (2, 4));
+///   },
+///   e => panic!("Unexpected error: {}", e),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum OsmiaError {
+	/// The code could not be split into tokens.
+	Lexer(ErrorInfo),
+	/// The tokens do not follow the grammar of the language.
+	Parser(ErrorInfo),
+	/// The code failed while being interpreted.
+	Runtime(ErrorInfo),
+	/// A key could not be read from or written into the context.
+	Ctx(ErrorInfo),
+	/// A value of the context could not be converted into the requested Rust type.
+	Conversion(ErrorInfo),
+	/// The code exceeded one of the configured execution limits.
+	Limit(LimitKind, ErrorInfo),
+}
+
+impl OsmiaError {
+	pub fn runtime(msg: impl Into<String>) -> Self {
+		Self::Runtime(ErrorInfo::new(msg))
+	}
+
+	pub fn ctx(msg: impl Into<String>, key_path: impl Into<String>) -> Self {
+		Self::Ctx(ErrorInfo::new(msg).with_culprit(key_path))
+	}
+
+	pub fn conversion(msg: impl Into<String>, key_path: impl Into<String>) -> Self {
+		Self::Conversion(ErrorInfo::new(msg).with_culprit(key_path))
+	}
+
+	pub fn limit(kind: LimitKind, max: usize) -> Self {
+		Self::Limit(kind, ErrorInfo::new(kind.message(max)))
+	}
+
+	pub fn info(&self) -> &ErrorInfo {
+		match self {
+			Self::Lexer(info) | Self::Parser(info) |
+			Self::Runtime(info) | Self::Ctx(info) |
+			Self::Conversion(info) | Self::Limit(_, info) => info,
+		}
+	}
+
+	pub fn message(&self) -> &str {
+		self.info().message()
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.info().location()
+	}
+
+	/// Sets the location of the error unless it is already known.
+	pub fn or_location(self, location: Option<Location>) -> Self {
+		self.map_info(|info| info.or_location(location))
+	}
+
+	/// Adds a snippet of the code pointing to the location of the error.
+	pub fn with_code_trace(self, code: &str) -> Self {
+		self.map_info(|info| info.with_code_trace(code))
+	}
+
+	fn map_info(self, f: impl FnOnce(ErrorInfo) -> ErrorInfo) -> Self {
+		match self {
+			Self::Lexer(info) => Self::Lexer(f(info)),
+			Self::Parser(info) => Self::Parser(f(info)),
+			Self::Runtime(info) => Self::Runtime(f(info)),
+			Self::Ctx(info) => Self::Ctx(f(info)),
+			Self::Conversion(info) => Self::Conversion(f(info)),
+			Self::Limit(kind, info) => Self::Limit(kind, f(info)),
+		}
+	}
+
+	fn prefix(&self) -> Option<&str> {
+		match self {
+			Self::Lexer(_) => Some("Lexer error"),
+			Self::Parser(_) => Some("Parser error"),
+			Self::Conversion(_) => Some("Conversion error"),
+			Self::Limit(_, _) => Some("Limit error"),
+			Self::Runtime(_) | Self::Ctx(_) => None,
+		}
+	}
+}
+
+impl Display for OsmiaError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let info = self.info();
+		if let Some(prefix) = self.prefix() {
+			write!(f, "{}: ", prefix)?;
+		}
+		if let Some(location) = info.location() {
+			write!(f, "Line {}: ", location.line())?;
+		}
+		write!(f, "{}", info.message())?;
+		if let Some(trace) = info.trace() {
+			write!(f, "\n{}", trace)?;
+		}
+		Ok(())
+	}
+}
+
+impl std::error::Error for OsmiaError {}
+
+impl From<String> for OsmiaError {
+	fn from(msg: String) -> Self {
+		Self::runtime(msg)
+	}
+}
+
+impl From<&str> for OsmiaError {
+	fn from(msg: &str) -> Self {
+		Self::runtime(msg)
+	}
+}
@@ -4,7 +4,8 @@ use super::*;
 pub struct Binary {
 	left: Box<Expr>,
 	operator: BinaryOp,
-	right: Box<Expr>
+	right: Box<Expr>,
+	location: NodeLocation,
 }
 
 impl Binary {
@@ -16,10 +17,20 @@ impl Binary {
 		Self {
 			left: Box::new(left),
 			operator: operator,
-			right: Box::new(right)
+			right: Box::new(right),
+			location: NodeLocation::default(),
 		}
 	}
 
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
 	pub fn left(&self) -> &Expr {
 		&self.left
 	}
@@ -19,7 +19,10 @@ pub enum BinaryOp {
 	Div,
 	Mod,
 	And,
-	Or
+	Or,
+	NullCoalesce,
+	Range,
+	RangeInclusive,
 }
 
 impl From<&Token> for Option<BinaryOp> {
@@ -43,6 +46,9 @@ impl From<&Token> for Option<BinaryOp> {
 			Token::Mod => BinaryOp::Mod,
 			Token::And => BinaryOp::And,
 			Token::Or => BinaryOp::Or,
+			Token::NullCoalesce => BinaryOp::NullCoalesce,
+			Token::Range => BinaryOp::Range,
+			Token::RangeInclusive => BinaryOp::RangeInclusive,
 			_ => return None
 		})
 	}
@@ -69,6 +75,9 @@ impl Display for BinaryOp {
 			BinaryOp::Mod => write!(f, "%"),
 			BinaryOp::And => write!(f, "&&"),
 			BinaryOp::Or => write!(f, "||"),
+			BinaryOp::NullCoalesce => write!(f, "??"),
+			BinaryOp::Range => write!(f, ".."),
+			BinaryOp::RangeInclusive => write!(f, "..="),
 		}
 	}
 }
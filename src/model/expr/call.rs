@@ -4,11 +4,21 @@ use super::*;
 pub struct Call {
 	pub callee: Box<Expr>,
 	pub args: Vec<Expr>,
+	location: NodeLocation,
 }
 
 impl Call {
 	pub fn new(callee: Expr, args: Vec<Expr>) -> Self {
-		Self { callee: Box::new(callee), args }
+		Self { callee: Box::new(callee), args, location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
 	}
 
 	pub fn callee(&self) -> &Expr {
@@ -6,6 +6,7 @@ pub enum Expr {
 	Binary(Binary),
 	Grouping(Grouping),
 	Unary(Unary),
+	Ternary(Ternary),
 
 	Lambda(Lambda),
 	Object(Object),
@@ -20,6 +21,7 @@ pub enum Expr {
 	Bool(bool),
 	Null,
 
+	Range(Range),
 	Callable(Callable),
 }
 
@@ -33,6 +35,7 @@ impl Expr {
 			Expr::Binary(_) => "binary",
 			Expr::Grouping(_) => "grouping",
 			Expr::Unary(_) => "unary",
+			Expr::Ternary(_) => "ternary",
 			Expr::Lambda(_) => "lambda",
 			Expr::Object(_) => "object",
 			Expr::Array(_) => "array",
@@ -44,12 +47,27 @@ impl Expr {
 			Expr::Str(_) => "string",
 			Expr::Bool(_) => "bool",
 			Expr::Null => "null",
+			Expr::Range(_) => "range",
 			Expr::Callable(c) => match c {
 				Callable::Function(_) | Callable::Builtin(_) => "function",
 				Callable::Lambda(_) => "lambda",
 			}
 		}.to_string()
 	}
+
+	/// Position in the code of the expression, if it was parsed from it.
+	pub fn location(&self) -> Option<Location> {
+		match self {
+			Expr::Binary(b) => b.location(),
+			Expr::Unary(u) => u.location(),
+			Expr::Ternary(t) => t.location(),
+			Expr::Call(c) => c.location(),
+			Expr::MethodCall(m) => m.location(),
+			Expr::Variable(v) => v.location(),
+			Expr::Grouping(g) => g.expr().location(),
+			_ => None,
+		}
+	}
 }
 
 impl From<Binary> for Expr {
@@ -64,6 +82,12 @@ impl From<Grouping> for Expr {
 	}
 }
 
+impl From<Ternary> for Expr {
+	fn from(t: Ternary) -> Self {
+		Self::Ternary(t)
+	}
+}
+
 impl From<Unary> for Expr {
 	fn from(u: Unary) -> Self {
 		Self::Unary(u)
@@ -100,6 +124,12 @@ impl From<MethodCall> for Expr {
 	}
 }
 
+impl From<Range> for Expr {
+	fn from(r: Range) -> Self {
+		Self::Range(r)
+	}
+}
+
 impl From<Variable> for Expr {
 	fn from(v: Variable) -> Self {
 		Self::Variable(v)
@@ -51,7 +51,7 @@ impl Add for Expr {
 			(Expr::Null, Expr::Null) => Ok(Expr::Null),
 			(Expr::Array(arr1), Expr::Array(arr2)) => Ok(Expr::Array(arr1 + arr2)),
 			(Expr::Object(obj1), Expr::Object(obj2)) => Ok(Expr::Object((obj1 | obj2)?)),
-			(s, rhs) => Err(format!("Don't know how to add {} and {}", s, rhs))
+			(s, rhs) => Err(format!("Don't know how to add {} and {}", s, rhs).into())
 		}
 	}
 }
@@ -88,7 +88,7 @@ impl Sub for Expr {
 					n1, n2
 				))?
 			)),
-			(s, rhs) => Err(format!("Don't know how to subtract {} and {}", s, rhs))
+			(s, rhs) => Err(format!("Don't know how to subtract {} and {}", s, rhs).into())
 		}
 	}
 }
@@ -115,7 +115,7 @@ impl Mul for Expr {
 					n1, n2
 				))?
 			)),
-			(s, rhs) => Err(format!("Don't know how to multiply {} and {}", s, rhs))
+			(s, rhs) => Err(format!("Don't know how to multiply {} and {}", s, rhs).into())
 		}
 	}
 }
@@ -131,8 +131,8 @@ impl Div for Expr {
 	/// assert_eq!(osmia.run_code("{{ 1 / 2 }}").unwrap(), "0");
 	/// assert_eq!(osmia.run_code("{{ 1.2 / 2 }}").unwrap(), "0.6");
 	/// assert_eq!(osmia.run_code("{{ 1 / 2.0 }}").unwrap(), "0.5");
-	/// assert!(osmia.run_code("{{ 1 / 0 }}").unwrap_err().contains("divide"));
-	/// assert!(osmia.run_code("{{ 0 / 0 }}").unwrap_err().contains("divide"));
+	/// assert!(osmia.run_code("{{ 1 / 0 }}").unwrap_err().to_string().contains("divide"));
+	/// assert!(osmia.run_code("{{ 0 / 0 }}").unwrap_err().to_string().contains("divide"));
 	/// ```
 	fn div(self, rhs: Self) -> Self::Output {
 		match (self, &rhs) {
@@ -145,7 +145,7 @@ impl Div for Expr {
 					n1, n2
 				))?
 			)),
-			(s, rhs) => Err(format!("Don't know how to divide {} and {}", s, rhs))
+			(s, rhs) => Err(format!("Don't know how to divide {} and {}", s, rhs).into())
 		}
 	}
 }
@@ -162,8 +162,8 @@ impl Rem for Expr {
 	/// assert_eq!(osmia.run_code("{{ 1.2 % 2 }}").unwrap(), "1.2");
 	/// assert_eq!(osmia.run_code("{{ 1 % 2.1 }}").unwrap(), "1");
 	/// assert_eq!(osmia.run_code("{{ 1.2 % 2.1 }}").unwrap(), "1.2");
-	/// assert!(osmia.run_code("{{ 1 % 0 }}").unwrap_err().contains("modulo"));
-	/// assert!(osmia.run_code("{{ 0 % 0 }}").unwrap_err().contains("modulo"));
+	/// assert!(osmia.run_code("{{ 1 % 0 }}").unwrap_err().to_string().contains("modulo"));
+	/// assert!(osmia.run_code("{{ 0 % 0 }}").unwrap_err().to_string().contains("modulo"));
 	/// ```
 	fn rem(self, rhs: Self) -> Self::Output {
 		match (self, &rhs) {
@@ -176,7 +176,7 @@ impl Rem for Expr {
 					n1, n2
 				))?
 			)),
-			(s, rhs) => Err(format!("Don't know how to modulo {} and {}", s, rhs))
+			(s, rhs) => Err(format!("Don't know how to modulo {} and {}", s, rhs).into())
 		}
 	}
 }
@@ -312,7 +312,7 @@ fn cast_int_for_operation(e: Expr, operation: &str) -> Result<i64, OsmiaError> {
 		Err(e) => Err(format!(
 			"Cannot execute operation {} on non-integer value: {}",
 			operation, e
-		))
+		).into())
 	}
 }
 
@@ -491,7 +491,7 @@ impl Shl for Expr {
 		match (&self, &rhs) {
 			(Expr::Str(s), Expr::Int(i)) => {
 				if *i < 0 {
-					return Err(format!("Invalid shift amount: {:?}", rhs));
+					return Err(format!("Invalid shift amount: {:?}", rhs).into());
 				}
 				let i: usize = *i as usize;
 				let s: &str = match i < s.len() {
@@ -535,7 +535,7 @@ impl Shr for Expr {
 		match (&self, &rhs) {
 			(Expr::Str(s), Expr::Int(i)) => {
 				if *i < 0 {
-					return Err(format!("Invalid shift amount: {:?}", rhs));
+					return Err(format!("Invalid shift amount: {:?}", rhs).into());
 				}
 				let i: usize = *i as usize;
 				let s: &str = match i < s.len() {
@@ -590,7 +590,7 @@ impl Neg for Expr {
 		match self {
 			Expr::Int(i) => Ok(Expr::Int(-i)),
 			Expr::Float(f) => Ok(Expr::Float(-f)),
-			_ => Err(format!("Cannot negate {}", self)),
+			_ => Err(format!("Cannot negate {}", self).into()),
 		}
 	}
 }
@@ -615,7 +615,7 @@ impl Affirm for Expr {
 	fn affirm(self) -> Self::Output {
 		match self {
 			Expr::Int(_) | Expr::Float(_) => Ok(self),
-			_ => Err(format!("Cannot affirm {}", self)),
+			_ => Err(format!("Cannot affirm {}", self).into()),
 		}
 	}
 }
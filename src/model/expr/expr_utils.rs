@@ -23,8 +23,10 @@ impl Display for Expr {
 			Expr::Binary(b) => Ok(write!(f, "{b}")?),
 			Expr::Grouping(g) => Ok(write!(f, "{g}")?),
 			Expr::Unary(u) => Ok(write!(f, "{u}")?),
+			Expr::Ternary(t) => Ok(write!(f, "{t}")?),
 			Expr::Variable(v) => Ok(write!(f, "{v}")?),
 			Expr::Lambda(l) => Ok(write!(f, "{l}")?),
+			Expr::Range(r) => Ok(write!(f, "{r}")?),
 			Expr::Callable(c) => Ok(write!(f, "{c}")?),
 			e => Ok(write!(f, "{e:?}")?)
 		}
@@ -49,9 +51,9 @@ impl Expr {
 			Expr::Int(i) => Ok(*i as f64),
 			Expr::Str(n) => match n.parse::<f64>() {
 				Ok(f) => Ok(f),
-				Err(_) => Err(format!("Cannot convert {:?} to float", n)),
+				Err(_) => Err(format!("Cannot convert {:?} to float", n).into()),
 			},
-			_ => Err(format!("Cannot convert {} to float", self))
+			_ => Err(format!("Cannot convert {} to float", self).into())
 		}
 	}
 
@@ -61,9 +63,9 @@ impl Expr {
 			Expr::Int(i) => Ok(*i),
 			Expr::Str(s) => match s.parse::<i64>() {
 				Ok(i) => Ok(i),
-				Err(_) => Err(format!("Cannot convert {:?} to int", s)),
+				Err(_) => Err(format!("Cannot convert {:?} to int", s).into()),
 			},
-			_ => Err(format!("Cannot convert {} to int", self))
+			_ => Err(format!("Cannot convert {} to int", self).into())
 		}
 	}
 
@@ -86,6 +88,7 @@ impl TryFrom<&JsonTree<String, CtxValue>> for Expr {
 				CtxValue::Str(s) => Ok(Expr::Str(s.clone())),
 				CtxValue::Bool(b) => Ok(Expr::Bool(*b)),
 				CtxValue::Null => Ok(Expr::Null),
+				CtxValue::Range(r) => Ok(Expr::Range(*r)),
 				CtxValue::Callable(c) => Ok(Expr::Callable(c.clone())),
 			},
 			JsonTree::Object(o) => {
@@ -131,15 +134,16 @@ impl TryFrom<&Expr> for JsonTree<String, CtxValue> {
 				for (e, v) in obj.entries() {
 					let key = match e {
 						Expr::Str(s) => s.into(),
-						_ => return Err("Object key must be a string".to_string()),
+						_ => return Err("Object key must be a string".into()),
 					};
 					let value = Box::new((&v).try_into()?);
 					items.insert(key, value);
 				}
 				JsonTree::Object(items)
 			},
+			Expr::Range(r) => JsonTree::Value(CtxValue::Range(*r)),
 			Expr::Callable(c) => JsonTree::Value(CtxValue::Callable(c.clone())),
-			_ => return Err(format!("The expression {:?} cannot be stored in the context", value)),
+			_ => return Err(format!("The expression {:?} cannot be stored in the context", value).into()),
 		})
 	}
 }
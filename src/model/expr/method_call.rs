@@ -4,10 +4,20 @@ use super::*;
 pub struct MethodCall {
 	pub obj: Box<Expr>,
 	pub call: Call,
+	location: NodeLocation,
 }
 
 impl MethodCall {
 	pub fn new(obj: Expr, call: Call) -> Self {
-		Self { obj: Box::new(obj), call }
+		Self { obj: Box::new(obj), call, location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
 	}
 }
@@ -10,6 +10,8 @@ mod json;
 mod json_tree_key_expr;
 mod lambda;
 mod method_call;
+mod range;
+mod ternary;
 mod unary_op;
 mod unary;
 mod variable;
@@ -24,6 +26,8 @@ pub use json::*;
 pub use json_tree_key_expr::JsonTreeKeyExpr;
 pub use lambda::Lambda;
 pub use method_call::MethodCall;
+pub use range::Range;
+pub use ternary::Ternary;
 pub use unary_op::UnaryOp;
 pub use unary::Unary;
 pub use variable::Variable;
@@ -31,5 +35,9 @@ pub use variable::Variable;
 use crate::model::lexer::Token;
 use crate::model::ctx::JsonTreeKey;
 use crate::model::interpreter::Callable;
+use crate::model::error::{
+	Location,
+	NodeLocation,
+};
 
 use std::fmt::Display;
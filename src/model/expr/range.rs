@@ -0,0 +1,136 @@
+use super::*;
+use crate::types::OsmiaError;
+use serde::Serialize;
+use serde::ser::{Error, SerializeSeq};
+use crate::constants::MAX_SERIALIZED_RANGE;
+
+/// Sequence of integers built with `start..end` or `start..=end`.
+///
+/// The items are computed when needed, so big ranges do not take any memory.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+	first: i64,
+	step: i64,
+	len: usize,
+}
+
+impl Range {
+	/// Range from `start` to `end`, excluded.
+	pub fn new(start: i64, end: i64) -> Result<Self, OsmiaError> {
+		match start < end {
+			true => Self::with_len(start, end as i128 - start as i128),
+			false => Ok(Self::from_parts(start, 1, 0)),
+		}
+	}
+
+	/// Range from `start` to `end`, included.
+	pub fn new_inclusive(start: i64, end: i64) -> Result<Self, OsmiaError> {
+		match start <= end {
+			true => Self::with_len(start, end as i128 - start as i128 + 1),
+			false => Ok(Self::from_parts(start, 1, 0)),
+		}
+	}
+
+	fn with_len(start: i64, len: i128) -> Result<Self, OsmiaError> {
+		let len = usize::try_from(len).map_err(|_| format!(
+			"The range starting at {} is too large", start
+		))?;
+		Ok(Self::from_parts(start, 1, len))
+	}
+
+	fn from_parts(first: i64, step: i64, len: usize) -> Self {
+		match len {
+			0 => Self { first: 0, step: 1, len },
+			1 => Self { first, step: 1, len },
+			_ => Self { first, step, len },
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Item at the given position.
+	///
+	/// The arithmetic wraps, but the result always fits as it is one of the items.
+	pub fn get(&self, i: usize) -> Option<i64> {
+		match i < self.len {
+			true => Some(self.first.wrapping_add(self.step.wrapping_mul(i as i64))),
+			false => None,
+		}
+	}
+
+	pub fn iter(&self) -> impl ExactSizeIterator<Item = i64> {
+		let range = *self;
+		(0..self.len).map(move |i| range.get(i).unwrap_or_else(|| unreachable!()))
+	}
+
+	/// Range with one of every `step` items.
+	pub fn step(&self, step: i64) -> Result<Self, OsmiaError> {
+		if step <= 0 {
+			return Err(format!("The step of a range must be a positive integer, got {}", step).into());
+		}
+		Ok(Self::from_parts(
+			self.first,
+			self.step.wrapping_mul(step),
+			self.len.div_ceil(step as usize),
+		))
+	}
+
+	pub fn reverse(&self) -> Self {
+		match self.get(self.len.saturating_sub(1)) {
+			Some(last) => Self::from_parts(last, self.step.wrapping_neg(), self.len),
+			None => *self,
+		}
+	}
+
+	pub fn contains(&self, value: i64) -> bool {
+		if self.is_empty() {
+			return false;
+		}
+		let distance = value as i128 - self.first as i128;
+		let step = self.step as i128;
+		distance % step == 0 && (0..self.len as i128).contains(&(distance / step))
+	}
+}
+
+impl Display for Range {
+	/// Shows the range as the code that builds it.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		let reversed = self.step < 0;
+		let (start, step) = match reversed {
+			true => (self.get(self.len - 1).unwrap_or(self.first), (self.step as i128).abs()),
+			false => (self.first, self.step as i128),
+		};
+		let end = start as i128 + step * (self.len as i128 - 1) + 1;
+		let end = end.max(start as i128);
+		match (step, reversed) {
+			(1, false) => write!(f, "{}..{}", start, end),
+			(1, true) => write!(f, "({}..{})?reverse()", start, end),
+			(step, false) => write!(f, "({}..{})?step({})", start, end, step),
+			(step, true) => write!(f, "({}..{})?step({})?reverse()", start, end, step),
+		}
+	}
+}
+
+impl Serialize for Range {
+	/// Serializes the range as the array of its items. Fails if it has more than
+	/// [MAX_SERIALIZED_RANGE] items.
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+		if self.len > MAX_SERIALIZED_RANGE {
+			return Err(S::Error::custom(format!(
+				"The range {} has {} items, more than the {} that can be serialized",
+				self, self.len, MAX_SERIALIZED_RANGE
+			)));
+		}
+		let mut seq = serializer.serialize_seq(Some(self.len))?;
+		for item in self.iter() {
+			seq.serialize_element(&item)?;
+		}
+		seq.end()
+	}
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+/// Inline conditional: `then if condition else otherwise`.
+///
+/// Only the chosen branch is evaluated.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ternary {
+	condition: Box<Expr>,
+	then: Box<Expr>,
+	otherwise: Box<Expr>,
+	location: NodeLocation,
+}
+
+impl Ternary {
+	pub fn new(condition: Expr, then: Expr, otherwise: Expr) -> Self {
+		Self {
+			condition: Box::new(condition),
+			then: Box::new(then),
+			otherwise: Box::new(otherwise),
+			location: NodeLocation::default(),
+		}
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
+	pub fn condition(&self) -> &Expr {
+		&self.condition
+	}
+
+	pub fn then(&self) -> &Expr {
+		&self.then
+	}
+
+	pub fn otherwise(&self) -> &Expr {
+		&self.otherwise
+	}
+}
+
+impl Display for Ternary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} if {} else {}", self.then, self.condition, self.otherwise)
+	}
+}
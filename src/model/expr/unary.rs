@@ -4,11 +4,21 @@ use super::*;
 pub struct Unary {
 	op: UnaryOp,
 	expr: Box<Expr>,
+	location: NodeLocation,
 }
 
 impl Unary {
 	pub fn new(op: UnaryOp, expr: Expr) -> Self {
-		Self { op, expr: Box::new(expr) }
+		Self { op, expr: Box::new(expr), location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
 	}
 
 	pub fn operator(&self) -> &UnaryOp {
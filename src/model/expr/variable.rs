@@ -3,11 +3,13 @@ use super::*;
 #[derive(Debug, PartialEq, Clone)]
 pub struct Variable {
 	var: Vec<JsonTreeKeyExpr>,
+	safe_keys: Vec<usize>,
+	location: NodeLocation,
 }
 
 impl Variable {
 	pub fn from_vec(var: Vec<JsonTreeKeyExpr>) -> Self {
-		Self { var }
+		Self { var, safe_keys: Vec::new(), location: NodeLocation::default() }
 	}
 
 	pub fn from_name(name: JsonTreeKeyExpr) -> Self {
@@ -18,6 +20,13 @@ impl Variable {
 		self.var.push(key)
 	}
 
+	/// Adds a key accessed with safe navigation (`?.`): if it cannot be
+	/// reached, the whole variable is null instead of an error.
+	pub fn push_safe(&mut self, key: JsonTreeKeyExpr) {
+		self.safe_keys.push(self.var.len());
+		self.var.push(key)
+	}
+
 	pub fn extend(&mut self, keys: Vec<JsonTreeKeyExpr>) {
 		self.var.extend(keys)
 	}
@@ -25,6 +34,31 @@ impl Variable {
 	pub fn vec(&self) -> &Vec<JsonTreeKeyExpr> {
 		&self.var
 	}
+
+	/// Indexes of the keys accessed with safe navigation.
+	pub fn safe_keys(&self) -> &Vec<usize> {
+		&self.safe_keys
+	}
+
+	pub fn is_safe(&self) -> bool {
+		!self.safe_keys.is_empty()
+	}
+
+	/// Keeps the safe navigation of this variable in the given one, which must
+	/// have the same keys.
+	pub fn with_safe_keys_of(mut self, other: &Variable) -> Self {
+		self.safe_keys = other.safe_keys.clone();
+		self
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
 }
 
 impl From<Variable> for Vec<JsonTreeKeyExpr> {
@@ -39,7 +73,11 @@ impl std::fmt::Display for Variable {
 		for (i, v) in self.var.iter().enumerate() {
 			brackets = false;
 			if i > 0 {
+				if self.safe_keys.contains(&i) {
+					write!(f, "?.")?;
+				}
 				match v {
+					JsonTreeKeyExpr::JsonTreeKey(JsonTreeKey::Key(_)) if self.safe_keys.contains(&i) => (),
 					JsonTreeKeyExpr::JsonTreeKey(JsonTreeKey::Key(_)) => write!(f, ".")?,
 					_ => {
 						brackets = true;
@@ -1,11 +1,12 @@
 use super::*;
 
 pub type BuiltinArg = fn(intpr: &OsmiaInterpreter<'_>, args: CallableArgs) -> Result<Expr, OsmiaError>;
+type BuiltinFn = Arc<dyn Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync>;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub struct Builtin {
 	arity: Option<usize>,
-	call: BuiltinArg,
+	call: BuiltinFn,
 	params: Option<Vec<FunctionParam>>,
 	#[cfg(feature = "detailed-dumper")]
 	description: String,
@@ -14,7 +15,7 @@ pub struct Builtin {
 impl Builtin {
 	fn raw(
 		arity: Option<usize>,
-		call: BuiltinArg,
+		call: BuiltinFn,
 		params: Option<Vec<FunctionParam>>,
 		#[cfg(feature = "detailed-dumper")] description: &str
 	) -> Self {
@@ -29,22 +30,34 @@ impl Builtin {
 
 	pub fn new(
 		arity: usize,
-		call: BuiltinArg,
+		call: impl Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync + 'static,
 		#[cfg(feature = "detailed-dumper")] description: &str
 	) -> Self {
 		Self::raw(
-			Some(arity), call, None,
+			Some(arity), Arc::new(call), None,
 			#[cfg(feature = "detailed-dumper")]
 			description
 		)
 	}
 
 	pub fn new_variable_args(
-		call: BuiltinArg,
+		call: impl Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync + 'static,
 		#[cfg(feature = "detailed-dumper")] description: &str
 	) -> Self {
 		Self::raw(
-			None, call, None,
+			None, Arc::new(call), None,
+			#[cfg(feature = "detailed-dumper")]
+			description
+		)
+	}
+
+	pub fn new_with_params(
+		params: Vec<FunctionParam>,
+		call: impl Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync + 'static,
+		#[cfg(feature = "detailed-dumper")] description: &str
+	) -> Self {
+		Self::raw(
+			Some(params.len()), Arc::new(call), Some(params),
 			#[cfg(feature = "detailed-dumper")]
 			description
 		)
@@ -68,6 +81,23 @@ impl Builtin {
 	}
 }
 
+impl PartialEq for Builtin {
+	fn eq(&self, other: &Self) -> bool {
+		self.arity == other.arity &&
+		self.params == other.params &&
+		Arc::ptr_eq(&self.call, &other.call)
+	}
+}
+
+impl std::fmt::Debug for Builtin {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Builtin")
+			.field("arity", &self.arity)
+			.field("params", &self.params)
+			.finish_non_exhaustive()
+	}
+}
+
 impl std::fmt::Display for Builtin {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(f, "{{ fn (...) => ... }}")
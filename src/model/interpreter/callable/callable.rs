@@ -9,7 +9,8 @@ pub enum Callable {
 
 impl Callable {
 	pub fn new(
-		arity: usize, call: BuiltinArg,
+		arity: usize,
+		call: impl Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync + 'static,
 		#[cfg(feature = "detailed-dumper")] description: &str
 	) -> Self {
 		Self::Builtin(Builtin::new(
@@ -20,7 +21,7 @@ impl Callable {
 	}
 
 	pub fn new_variable_args(
-		call: BuiltinArg,
+		call: impl Fn(&OsmiaInterpreter<'_>, CallableArgs) -> Result<Expr, OsmiaError> + Send + Sync + 'static,
 		#[cfg(feature = "detailed-dumper")] description: &str
 	) -> Self {
 		Self::Builtin(Builtin::new_variable_args(
@@ -46,7 +47,7 @@ impl Callable {
 		return format!(
 			"Expected {} arguments, got {}",
 			arity, argc
-		);
+		).into();
 	}
 
 	pub fn call(&self, intpr: &OsmiaInterpreter<'_>, args: &Vec<Expr>) -> Result<Expr, OsmiaError> {
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use crate::OsmiaInterpreter;
 use crate::types::{
@@ -20,6 +21,7 @@ mod callable;
 mod builtin;
 mod lambda_callable;
 mod function_callable;
+mod native_fn;
 
 pub use callable::Callable;
 pub use builtin::{
@@ -28,5 +30,6 @@ pub use builtin::{
 };
 pub use lambda_callable::LambdaCallable;
 pub use function_callable::FunctionCallable;
+pub use native_fn::NativeFn;
 
 type CallableArgs<'c> = &'c Vec<Expr>;
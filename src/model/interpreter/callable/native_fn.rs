@@ -0,0 +1,152 @@
+use serde::Deserialize;
+use serde_json::Value;
+use super::*;
+use crate::model::ctx::{
+	CtxValue,
+	JsonTree,
+	JsonTreeKey,
+};
+
+pub type NativeFnArg = Arc<dyn Fn(&[Value]) -> Result<Value, OsmiaError> + Send + Sync>;
+
+/// Rust closure that can be called from the templates.
+///
+/// The arguments and the result are exchanged as [serde_json::Value].
+/// By default, the function accepts any number of arguments.
+///
+/// ```rust
+/// use osmia::{Osmia, NativeFn};
+/// use serde_json::json;
+///
+/// let mut osmia = Osmia::default();
+/// osmia.register_native_fn("greet", NativeFn::new(|args| {
+///   Ok(json!(format!("{}, {}!", args[1].as_str().unwrap(), args[0].as_str().unwrap())))
+/// }).param("name").param_or("greeting", json!("Hello"))).unwrap();
+/// assert_eq!(osmia.run_code("{{ greet(\"Marvin\") }}").unwrap(), "Hello, Marvin!");
+/// assert_eq!(osmia.run_code("{{ greet(\"Marvin\", \"Bye\") }}").unwrap(), "Bye, Marvin!");
+/// ```
+#[derive(Clone)]
+pub struct NativeFn {
+	call: NativeFnArg,
+	arity: Option<usize>,
+	params: Vec<(String, Option<Value>)>,
+	spread: Option<String>,
+	#[cfg(feature = "detailed-dumper")]
+	description: String,
+}
+
+impl NativeFn {
+	pub fn new(
+		call: impl Fn(&[Value]) -> Result<Value, OsmiaError> + Send + Sync + 'static
+	) -> Self {
+		Self {
+			call: Arc::new(call),
+			arity: None,
+			params: Vec::new(),
+			spread: None,
+			#[cfg(feature = "detailed-dumper")]
+			description: String::new(),
+		}
+	}
+
+	/// Expects exactly `arity` arguments.
+	pub fn arity(mut self, arity: usize) -> Self {
+		self.arity = Some(arity);
+		self
+	}
+
+	/// Adds a required parameter.
+	pub fn param(mut self, name: &str) -> Self {
+		self.params.push((name.into(), None));
+		self
+	}
+
+	/// Adds a parameter that takes the given value when the argument is omitted.
+	pub fn param_or(mut self, name: &str, default: Value) -> Self {
+		self.params.push((name.into(), Some(default)));
+		self
+	}
+
+	/// Collects the remaining arguments into an array.
+	pub fn spread(mut self, name: &str) -> Self {
+		self.spread = Some(name.into());
+		self
+	}
+
+	#[cfg(feature = "detailed-dumper")]
+	pub fn description(mut self, description: &str) -> Self {
+		self.description = description.into();
+		self
+	}
+
+	pub(crate) fn into_callable(self) -> Result<Callable, OsmiaError> {
+		let f = self.call;
+		let call = move |_: &OsmiaInterpreter<'_>, args: CallableArgs| {
+			let args = args.iter()
+				.map(Self::to_value)
+				.collect::<Result<Vec<Value>, OsmiaError>>()?;
+			Self::to_expr(f(&args)?)
+		};
+		let builtin = match (self.params.is_empty() && self.spread.is_none(), self.arity) {
+			(true, Some(arity)) => Builtin::new(
+				arity, call,
+				#[cfg(feature = "detailed-dumper")]
+				&self.description
+			),
+			(true, None) => Builtin::new_variable_args(
+				call,
+				#[cfg(feature = "detailed-dumper")]
+				&self.description
+			),
+			(false, _) => {
+				let mut params = Vec::with_capacity(self.params.len() + 1);
+				for (name, default) in self.params {
+					let default = match default {
+						Some(d) => Some(Self::to_expr(d)?),
+						None => None,
+					};
+					params.push(FunctionParam::new(JsonTreeKey::Key(name), default));
+				}
+				if let Some(name) = self.spread {
+					params.push(FunctionParam::new_spread(JsonTreeKey::Key(name)));
+				}
+				Builtin::new_with_params(
+					params, call,
+					#[cfg(feature = "detailed-dumper")]
+					&self.description
+				)
+			}
+		};
+		Ok(Callable::Builtin(builtin))
+	}
+
+	fn to_value(expr: &Expr) -> Result<Value, OsmiaError> {
+		let tree: JsonTree<String, CtxValue> = expr.try_into()?;
+		serde_json::to_value(tree)
+			.map_err(|e| format!("Invalid argument: {}", e).into())
+	}
+
+	fn to_expr(value: Value) -> Result<Expr, OsmiaError> {
+		match JsonTree::<String, CtxValue>::deserialize(value) {
+			Ok(tree) => Expr::try_from(&tree),
+			Err(e) => Err(format!("Invalid result: {}", e).into()),
+		}
+	}
+}
+
+impl<F> From<F> for NativeFn
+where F: Fn(&[Value]) -> Result<Value, OsmiaError> + Send + Sync + 'static {
+	fn from(call: F) -> Self {
+		Self::new(call)
+	}
+}
+
+impl std::fmt::Debug for NativeFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("NativeFn")
+			.field("arity", &self.arity)
+			.field("params", &self.params)
+			.field("spread", &self.spread)
+			.finish_non_exhaustive()
+	}
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+/// Escaping applied to every value printed with `{{ value }}`.
+///
+/// The raw text of the template and the output of the functions defined in
+/// it are never escaped. A value can be printed as is with `?safe()`.
+///
+/// ```rust
+/// use osmia::{Osmia, EscapeMode};
+///
+/// let mut osmia = Osmia::try_from(r#"{ "name": "<b>Marvin</b>" }"#).unwrap()
+///   .with_escape(EscapeMode::Html);
+/// assert_eq!(osmia.run_code("<p>{{ name }}</p>").unwrap(), "<p>&lt;b&gt;Marvin&lt;/b&gt;</p>");
+/// assert_eq!(osmia.run_code("<p>{{ name?safe() }}</p>").unwrap(), "<p><b>Marvin</b></p>");
+/// ```
+#[derive(Clone, Default)]
+pub enum EscapeMode {
+	/// Values are printed as they are.
+	#[default]
+	None,
+	/// Escapes `&`, `<`, `>`, `"` and `'` as HTML entities.
+	Html,
+	/// Escapes `&`, `<`, `>`, `"` and `'` as XML entities.
+	Xml,
+	/// Escapes the value to be placed inside a JSON string.
+	JsonString,
+	/// Quotes the value as a single POSIX shell word.
+	Shell,
+	/// Escapes the value with the given function.
+	Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl EscapeMode {
+	pub fn custom(escaper: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+		Self::Custom(Arc::new(escaper))
+	}
+
+	pub fn escape(&self, value: &str) -> String {
+		match self {
+			EscapeMode::None => value.to_string(),
+			EscapeMode::Html => Self::replace_chars(value, |c| match c {
+				'&' => Some("&amp;"),
+				'<' => Some("&lt;"),
+				'>' => Some("&gt;"),
+				'"' => Some("&quot;"),
+				'\'' => Some("&#x27;"),
+				_ => None,
+			}),
+			EscapeMode::Xml => Self::replace_chars(value, |c| match c {
+				'&' => Some("&amp;"),
+				'<' => Some("&lt;"),
+				'>' => Some("&gt;"),
+				'"' => Some("&quot;"),
+				'\'' => Some("&apos;"),
+				_ => None,
+			}),
+			EscapeMode::JsonString => {
+				let quoted = serde_json::Value::from(value).to_string();
+				quoted[1..quoted.len() - 1].to_string()
+			},
+			EscapeMode::Shell => format!("'{}'", value.replace('\'', r"'\''")),
+			EscapeMode::Custom(escaper) => escaper(value),
+		}
+	}
+
+	fn replace_chars(value: &str, replacement: impl Fn(char) -> Option<&'static str>) -> String {
+		let mut escaped = String::with_capacity(value.len());
+		for c in value.chars() {
+			match replacement(c) {
+				Some(r) => escaped.push_str(r),
+				None => escaped.push(c),
+			}
+		}
+		escaped
+	}
+}
+
+impl std::fmt::Debug for EscapeMode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			EscapeMode::None => write!(f, "None"),
+			EscapeMode::Html => write!(f, "Html"),
+			EscapeMode::Xml => write!(f, "Xml"),
+			EscapeMode::JsonString => write!(f, "JsonString"),
+			EscapeMode::Shell => write!(f, "Shell"),
+			EscapeMode::Custom(_) => write!(f, "Custom"),
+		}
+	}
+}
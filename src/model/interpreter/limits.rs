@@ -0,0 +1,70 @@
+/// Bounds enforced while interpreting the code.
+///
+/// Every limit is disabled by default. When one of them is exceeded, the
+/// interpretation stops with an [OsmiaError::Limit](crate::OsmiaError::Limit).
+///
+/// ```rust
+/// use osmia::{Osmia, OsmiaError, Limits, LimitKind};
+///
+/// let mut osmia = Osmia::default().with_limits(Limits::new().with_fuel(1000));
+/// match osmia.run_code("{{while true}}{{done}}").unwrap_err() {
+///   OsmiaError::Limit(LimitKind::Fuel, _) => (),
+///   e => panic!("Unexpected error: {}", e),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+	fuel: Option<usize>,
+	call_depth: Option<usize>,
+	output: Option<usize>,
+	collection_size: Option<usize>,
+}
+
+impl Limits {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Maximum number of statements and expressions evaluated.
+	pub fn with_fuel(mut self, fuel: usize) -> Self {
+		self.fuel = Some(fuel);
+		self
+	}
+
+	/// Maximum number of nested function calls.
+	///
+	/// Every call uses the stack of the thread running the code, so the limit
+	/// must be low enough for the recursion to fit in it.
+	pub fn with_call_depth(mut self, depth: usize) -> Self {
+		self.call_depth = Some(depth);
+		self
+	}
+
+	/// Maximum size of the output, in bytes.
+	pub fn with_output(mut self, bytes: usize) -> Self {
+		self.output = Some(bytes);
+		self
+	}
+
+	/// Maximum number of elements of an array or object.
+	pub fn with_collection_size(mut self, size: usize) -> Self {
+		self.collection_size = Some(size);
+		self
+	}
+
+	pub fn fuel(&self) -> Option<usize> {
+		self.fuel
+	}
+
+	pub fn call_depth(&self) -> Option<usize> {
+		self.call_depth
+	}
+
+	pub fn output(&self) -> Option<usize> {
+		self.output
+	}
+
+	pub fn collection_size(&self) -> Option<usize> {
+		self.collection_size
+	}
+}
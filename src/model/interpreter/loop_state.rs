@@ -0,0 +1,43 @@
+use crate::model::expr::{
+	Expr,
+	Object,
+};
+use crate::types::OsmiaError;
+
+/// Position of a `for` loop, exposed to its body as the `loop` variable.
+#[derive(Debug, Clone)]
+pub struct LoopState {
+	index: usize,
+	length: usize,
+}
+
+impl LoopState {
+	pub fn new(length: usize) -> Self {
+		Self { index: 0, length }
+	}
+
+	pub fn set_index(&mut self, index: usize) {
+		self.index = index;
+	}
+
+	/// Builds the `loop` object, with the one of the enclosing loop as `outer`.
+	pub fn to_expr(&self, outer: Expr) -> Result<Expr, OsmiaError> {
+		let revindex0 = self.length - self.index - 1;
+		Ok(Expr::Object(Object::new_hash(vec![
+			(Expr::new_str("index"), Self::int(self.index + 1)?),
+			(Expr::new_str("index0"), Self::int(self.index)?),
+			(Expr::new_str("revindex"), Self::int(revindex0 + 1)?),
+			(Expr::new_str("revindex0"), Self::int(revindex0)?),
+			(Expr::new_str("first"), Expr::Bool(self.index == 0)),
+			(Expr::new_str("last"), Expr::Bool(revindex0 == 0)),
+			(Expr::new_str("length"), Self::int(self.length)?),
+			(Expr::new_str("outer"), outer),
+		])?))
+	}
+
+	fn int(value: usize) -> Result<Expr, OsmiaError> {
+		i64::try_from(value).map(Expr::Int).map_err(|_| OsmiaError::runtime(format!(
+			"The position {} of the loop does not fit in an int", value
+		)))
+	}
+}
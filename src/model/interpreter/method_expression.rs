@@ -9,6 +9,7 @@ pub enum MethodExpression {
 	Null,
 	Array,
 	Object,
+	Range,
 	Callable,
 	Lambda
 }
@@ -24,6 +25,7 @@ impl TryFrom<&Expr> for MethodExpression {
 			Expr::Null => Ok(MethodExpression::Null),
 			Expr::Array(_) => Ok(MethodExpression::Array),
 			Expr::Object(_) => Ok(MethodExpression::Object),
+			Expr::Range(_) => Ok(MethodExpression::Range),
 			Expr::Callable(_) => Ok(MethodExpression::Callable),
 			Expr::Lambda(_) => Ok(MethodExpression::Lambda),
 			_ => Err(format!("Could not convert expression {} to method expression", expr)),
@@ -41,6 +43,7 @@ impl<'a> Into<&'a str> for MethodExpression {
 			MethodExpression::Null => "null",
 			MethodExpression::Array => "array",
 			MethodExpression::Object => "object",
+			MethodExpression::Range => "range",
 			MethodExpression::Callable => "callable",
 			MethodExpression::Lambda => "lambda",
 		}
@@ -57,6 +60,7 @@ impl Into<String> for &MethodExpression {
 			MethodExpression::Null => "null".into(),
 			MethodExpression::Array => "array".into(),
 			MethodExpression::Object => "object".into(),
+			MethodExpression::Range => "range".into(),
 			MethodExpression::Callable => "callable".into(),
 			MethodExpression::Lambda => "lambda".into(),
 		}
@@ -0,0 +1,49 @@
+use super::MethodExpression;
+
+/// Type of the values a custom method can be called on.
+///
+/// [MethodType::Any] registers the method for every type, like the generic
+/// methods of the stdlib (`len`, `to_string`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodType {
+	Str,
+	Int,
+	Float,
+	Bool,
+	Null,
+	Array,
+	Object,
+	Range,
+	Callable,
+	Lambda,
+	Any,
+}
+
+impl MethodType {
+	pub(crate) fn expressions(&self) -> Vec<MethodExpression> {
+		match self {
+			MethodType::Str => vec![MethodExpression::Str],
+			MethodType::Int => vec![MethodExpression::Int],
+			MethodType::Float => vec![MethodExpression::Float],
+			MethodType::Bool => vec![MethodExpression::Bool],
+			MethodType::Null => vec![MethodExpression::Null],
+			MethodType::Array => vec![MethodExpression::Array],
+			MethodType::Object => vec![MethodExpression::Object],
+			MethodType::Range => vec![MethodExpression::Range],
+			MethodType::Callable => vec![MethodExpression::Callable],
+			MethodType::Lambda => vec![MethodExpression::Lambda],
+			MethodType::Any => vec![
+				MethodExpression::Str,
+				MethodExpression::Int,
+				MethodExpression::Float,
+				MethodExpression::Bool,
+				MethodExpression::Null,
+				MethodExpression::Array,
+				MethodExpression::Object,
+				MethodExpression::Range,
+				MethodExpression::Callable,
+				MethodExpression::Lambda,
+			],
+		}
+	}
+}
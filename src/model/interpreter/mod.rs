@@ -1,13 +1,23 @@
 pub mod callable;
+mod escape_mode;
 mod exit_status;
 mod interpreter;
+mod limits;
+mod loop_state;
 mod osmia_interpreter;
 mod osmia_result;
 mod method_expression;
+mod method_type;
+mod print_sink;
 
 pub use callable::Callable;
+pub use escape_mode::EscapeMode;
 use exit_status::ExitStatus;
 pub use interpreter::Interpreter;
+pub use limits::Limits;
+use loop_state::LoopState;
 pub use osmia_interpreter::OsmiaInterpreter;
 pub use osmia_result::OsmiaResult;
 pub use method_expression::MethodExpression;
+pub use method_type::MethodType;
+pub use print_sink::PrintSink;
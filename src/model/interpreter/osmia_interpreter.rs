@@ -1,4 +1,7 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::rc::Rc;
 
 use crate::types::*;
 use super::{
@@ -6,20 +9,54 @@ use super::{
 	ExitStatus,
 	callable::*,
 	OsmiaResult,
-	MethodExpression
+	MethodExpression,
+	Limits,
+	EscapeMode,
+	PrintSink,
+	LoopState,
 };
+use crate::model::error::LimitKind;
+use crate::model::lexer::Syntax;
 use crate::utils::{
 	Affirm,
 };
-use crate::constants::METHOD_CTX_LOCATION;
+use crate::constants::{
+	LOOP_VARIABLE,
+	METHOD_CTX_LOCATION,
+	SAFE_METHOD,
+};
 use crate::ctx::{
 	JsonTree,
 	JsonTreeKey,
 	CtxValue,
 };
+use crate::model::loader::{
+	TemplateCache,
+	TemplateLoader,
+	TemplateResolver,
+};
+
+/// Keys and items of the iterable of a `for`.
+type ForItems = Box<dyn ExactSizeIterator<Item = (Expr, Expr)>>;
 
 pub struct OsmiaInterpreter<'ctx> {
 	pub ctx: CtxRef<'ctx>,
+	loader: Option<&'ctx dyn TemplateLoader>,
+	template_cache: Option<&'ctx TemplateCache>,
+	includes: RefCell<Vec<String>>,
+	limits: Limits,
+	steps: Cell<usize>,
+	call_depth: Cell<usize>,
+	writer: RefCell<Option<&'ctx mut dyn Write>>,
+	captures: RefCell<Vec<String>>,
+	written: Cell<usize>,
+	escape: EscapeMode,
+	syntax: Syntax,
+	print_sink: PrintSink,
+	printed: RefCell<Vec<String>>,
+	files: RefCell<BTreeMap<String, String>>,
+	loops: RefCell<Vec<LoopState>>,
+	captured: RefCell<Vec<(Vec<JsonTreeKey<String>>, String)>>,
 }
 
 
@@ -27,16 +64,178 @@ impl<'ctx> OsmiaInterpreter<'ctx> {
 	pub fn new(ctx: &'ctx mut Ctx) -> Self {
 		Self {
 			ctx: RefCell::new(ctx),
+			loader: None,
+			template_cache: None,
+			includes: RefCell::new(Vec::new()),
+			limits: Limits::default(),
+			steps: Cell::new(0),
+			call_depth: Cell::new(0),
+			writer: RefCell::new(None),
+			captures: RefCell::new(Vec::new()),
+			written: Cell::new(0),
+			escape: EscapeMode::default(),
+			syntax: Syntax::default(),
+			print_sink: PrintSink::default(),
+			printed: RefCell::new(Vec::new()),
+			files: RefCell::new(BTreeMap::new()),
+			loops: RefCell::new(Vec::new()),
+			captured: RefCell::new(Vec::new()),
+		}
+	}
+
+	pub fn with_loader(mut self, loader: Option<&'ctx dyn TemplateLoader>) -> Self {
+		self.loader = loader;
+		self
+	}
+
+	/// Keeps the included templates in the cache, instead of loading them every time.
+	pub fn with_template_cache(mut self, template_cache: &'ctx TemplateCache) -> Self {
+		self.template_cache = Some(template_cache);
+		self
+	}
+
+	pub fn with_limits(mut self, limits: Limits) -> Self {
+		self.limits = limits;
+		self
+	}
+
+	pub fn with_escape(mut self, escape: EscapeMode) -> Self {
+		self.escape = escape;
+		self
+	}
+
+	pub fn with_print_sink(mut self, print_sink: PrintSink) -> Self {
+		self.print_sink = print_sink;
+		self
+	}
+
+	/// Takes the values printed so far when using [PrintSink::Buffer].
+	pub fn take_printed(&self) -> Vec<String> {
+		self.printed.take()
+	}
+
+	/// Takes the content rendered so far by the file blocks, by path.
+	pub fn take_files(&self) -> BTreeMap<String, String> {
+		self.files.take()
+	}
+
+	/// Sets the [Syntax] used to lex the included templates.
+	pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+		self.syntax = syntax;
+		self
+	}
+
+	/// Sets the sink where [OsmiaInterpreter::render_code] writes the output.
+	pub fn with_writer(self, writer: &'ctx mut dyn Write) -> Self {
+		*self.writer.borrow_mut() = Some(writer);
+		self
+	}
+
+	fn consume_fuel(&self) -> Result<(), OsmiaError> {
+		let steps = self.steps.get() + 1;
+		self.steps.set(steps);
+		match self.limits.fuel() {
+			Some(max) if steps > max => Err(OsmiaError::limit(LimitKind::Fuel, max)),
+			_ => Ok(()),
+		}
+	}
+
+	/// Writes the output into the innermost capture or, if there is none, into the writer.
+	fn write_output(&self, output: &str) -> Result<(), OsmiaError> {
+		if output.is_empty() {
+			return Ok(());
+		}
+		let mut captures = self.captures.borrow_mut();
+		let size = match captures.last() {
+			Some(capture) => capture.len(),
+			None => self.written.get(),
+		} + output.len();
+		if let Some(max) = self.limits.output() {
+			if size > max {
+				return Err(OsmiaError::limit(LimitKind::Output, max));
+			}
+		}
+		match captures.last_mut() {
+			Some(capture) => capture.push_str(output),
+			None => {
+				if let Some(writer) = self.writer.borrow_mut().as_mut() {
+					writer.write_all(output.as_bytes())
+						.map_err(|e| format!("Unable to write the output: {}", e))?;
+				}
+				self.written.set(size);
+			},
+		}
+		Ok(())
+	}
+
+	fn write_result(&self, result: &OsmiaResult) -> Result<(), OsmiaError> {
+		match result {
+			OsmiaResult::None => Ok(()),
+			OsmiaResult::OsmiaOutput(o) => self.write_output(o),
+			OsmiaResult::Expr(e) => self.write_output(&self.escape.escape(&e.to_string())),
+		}
+	}
+
+	/// Runs `f` collecting the output it writes instead of sending it to the writer.
+	fn capture<T>(&self, f: impl FnOnce() -> Result<T, OsmiaError>) -> Result<(T, String), OsmiaError> {
+		self.captures.borrow_mut().push(String::new());
+		let result = f();
+		let output = self.captures.borrow_mut().pop().unwrap_or_default();
+		Ok((result?, output))
+	}
+
+	fn range(
+		start: &Expr,
+		end: &Expr,
+		new: impl Fn(i64, i64) -> Result<Range, OsmiaError>,
+	) -> ExprResult {
+		match (start, end) {
+			(Expr::Int(start), Expr::Int(end)) => Ok(new(*start, *end)?.into()),
+			_ => Err(format!(
+				"The bounds of a range must be integers, got {} and {}",
+				start.r#type(), end.r#type()
+			).into()),
+		}
+	}
+
+	fn check_collection(&self, expr: Expr) -> ExprResult {
+		let size = match &expr {
+			Expr::Array(arr) => arr.len(),
+			Expr::Object(obj) => obj.len(),
+			_ => return Ok(expr),
+		};
+		match self.limits.collection_size() {
+			Some(max) if size > max => Err(OsmiaError::limit(LimitKind::CollectionSize, max)),
+			_ => Ok(expr),
 		}
 	}
 }
 
 impl Interpreter<ParserCode, OsmiaOutput, OsmiaError> for OsmiaInterpreter<'_> {
 	fn interpret(&self, code: ParserCode) -> Result<OsmiaOutput, OsmiaError> {
-		match (&code).accept(self)? {
-			(ExitStatus::Okay, r) => Ok(r.to_string()),
+		self.interpret_code(&code)
+	}
+}
+
+impl OsmiaInterpreter<'_> {
+	pub fn interpret_code(&self, code: &ParserCode) -> Result<OsmiaOutput, OsmiaError> {
+		let ((), output) = self.capture(|| self.run(code))?;
+		Ok(output)
+	}
+
+	/// Interprets the code writing the output into the writer as it is generated.
+	pub fn render_code(&self, code: &ParserCode) -> Result<(), OsmiaError> {
+		self.run(code)?;
+		if let Some(writer) = self.writer.borrow_mut().as_mut() {
+			writer.flush().map_err(|e| format!("Unable to write the output: {}", e))?;
+		}
+		Ok(())
+	}
+
+	fn run(&self, code: &ParserCode) -> Result<(), OsmiaError> {
+		match code.accept(self)? {
 			(ExitStatus::Break, _) | (ExitStatus::Continue, _) => Err("Cannot break or continue out of the program".into()),
-			(ExitStatus::Return, r) => Ok(r.to_string()),
+			(ExitStatus::Okay, r) | (ExitStatus::Return, r) => self.write_result(&r),
 		}
 	}
 }
@@ -57,14 +256,13 @@ type ExprResult = Result<Expr, OsmiaError>;
 
 impl Visitor<StmtResult, ExprResult> for OsmiaInterpreter<'_> {
 	fn visit_stmt(&self, stmt: &Stmt) -> StmtResult {
-		match stmt {
+		let result = self.consume_fuel().and_then(|_| match stmt {
 			Stmt::Raw(s) => Ok((ExitStatus::Okay, OsmiaResult::OsmiaOutput(s.into()))),
 			Stmt::NonPrintable(_) => Ok((ExitStatus::Okay, OsmiaResult::None)),
 			Stmt::Block(b) => self.visit_block(b),
-			// TODO keep track of line
 			Stmt::NewLine => Ok((ExitStatus::Okay, OsmiaResult::OsmiaOutput("\n".into()))),
 			Stmt::NewLineNonPrintable => Ok((ExitStatus::Okay, OsmiaResult::None)),
-			Stmt::Expr(e) => Ok((ExitStatus::Okay, OsmiaResult::Expr(e.accept(self)?))),
+			Stmt::Expr(e) => Ok((ExitStatus::Okay, self.visit_output(e)?)),
 			Stmt::Print(s) => self.visit_print(s),
 			Stmt::Comment(_) => Ok((ExitStatus::Okay, OsmiaResult::None)),
 			Stmt::Assign(a) => self.visit_assign(a),
@@ -75,44 +273,54 @@ impl Visitor<StmtResult, ExprResult> for OsmiaInterpreter<'_> {
 			Stmt::Continue => Ok((ExitStatus::Continue, OsmiaResult::None)),
 			Stmt::Return(r) => self.visit_return(r),
 			Stmt::Function(f) => self.visit_function(f),
-		}
+			Stmt::Include(i) => self.visit_include(i),
+			Stmt::NamedBlock(b) => b.body().accept(self),
+			Stmt::Capture(c) => self.visit_capture(c),
+			Stmt::FileBlock(f) => self.visit_file_block(f),
+			Stmt::Extends(_) => Err("Extends can only be used at the top level of a template".into()),
+			Stmt::Parent => Err("Parent can only be used inside a block that overrides another one".into()),
+		});
+		result.map_err(|e| e.or_location(stmt.location()))
 	}
 
 	fn visit_expr(&self, expr: &Expr) -> ExprResult {
-		Ok(match expr {
-			Expr::Float(_) | Expr::Int(_) | Expr::Str(_) | Expr::Bool(_) | Expr::Null => expr.clone(),
-			Expr::Binary(b) => self.visit_binary(b)?,
-			Expr::Grouping(g) => self.visit_grouping(g)?,
-			Expr::Unary(u) => self.visit_unary(u)?,
-			Expr::Array(arr) => self.visit_array(arr)?,
-			Expr::Object(obj) => self.visit_object(obj)?,
-			Expr::Lambda(l) => self.visit_lambda(l)?,
-			Expr::Call(c) => self.visit_call(c)?,
-			Expr::MethodCall(m) => self.visit_method_call(m)?,
-			Expr::Variable(v) => self.get_variable(v)?,
-			Expr::Callable(_) => expr.clone(),
-		})
+		let result = self.consume_fuel().and_then(|_| match expr {
+			Expr::Float(_) | Expr::Int(_) | Expr::Str(_) | Expr::Bool(_) | Expr::Null => Ok(expr.clone()),
+			Expr::Binary(b) => self.visit_binary(b),
+			Expr::Grouping(g) => self.visit_grouping(g),
+			Expr::Unary(u) => self.visit_unary(u),
+			Expr::Ternary(t) => self.visit_ternary(t),
+			Expr::Array(arr) => self.visit_array(arr),
+			Expr::Object(obj) => self.visit_object(obj),
+			Expr::Lambda(l) => self.visit_lambda(l),
+			Expr::Call(c) => self.visit_call(c),
+			Expr::MethodCall(m) => self.visit_method_call(m),
+			Expr::Variable(v) => self.get_variable(v),
+			Expr::Range(_) | Expr::Callable(_) => Ok(expr.clone()),
+		});
+		result.map_err(|e| e.or_location(expr.location()))
 	}
 }
 
 impl OsmiaInterpreter<'_> {
 	fn visit_block(&self, block: &Block) -> StmtResult {
 		let mut state = ExitStatus::Okay;
-		let mut content = String::new();
 		for s in block.stmts() {
 			let (status, r) = s.accept(self)?;
-			content += r.to_string().as_str();
 			state = status;
 			match &state {
-				ExitStatus::Okay => (),
-				ExitStatus::Break | ExitStatus::Continue => break,
+				ExitStatus::Okay => self.write_result(&r)?,
+				ExitStatus::Break | ExitStatus::Continue => {
+					self.write_result(&r)?;
+					break;
+				},
 				ExitStatus::Return => match r {
 					OsmiaResult::None => break,
 					_ => return Ok((state, r)),
 				}
 			}
 		}
-		Ok((state, content.into()))
+		Ok((state, String::new().into()))
 	}
 
 	fn visit_if(&self, if_stmt: &If) -> StmtResult {
@@ -134,19 +342,20 @@ impl OsmiaInterpreter<'_> {
 	}
 
 	fn visit_while(&self, while_stmt: &While) -> StmtResult {
-		let mut content = String::new();
 		while let Some((status, r)) = self.visit_conditional(while_stmt)? {
-			content.push_str(r.to_string().as_str());
 			match status {
-				ExitStatus::Okay | ExitStatus::Continue => (),
-				ExitStatus::Break => break,
+				ExitStatus::Okay | ExitStatus::Continue => self.write_result(&r)?,
+				ExitStatus::Break => {
+					self.write_result(&r)?;
+					break;
+				},
 				ExitStatus::Return => match r {
 					OsmiaResult::None => break,
 					_ => return Ok((status, r)),
 				}
 			}
 		}
-		Ok((ExitStatus::Okay, content.into()))
+		Ok((ExitStatus::Okay, String::new().into()))
 	}
 
 	fn visit_conditional(&self, conditional: &ConditionalStmt) -> Result<Option<StmtOutput>, OsmiaError> {
@@ -157,28 +366,47 @@ impl OsmiaInterpreter<'_> {
 	}
 
 	fn visit_for(&self, for_stmt: &For) -> StmtResult {
-		let var = Self::var_arr_to_ctx_variable(self.visit_variable(for_stmt.variable())?.vec())?;
-		let iterable = self.visit_iterable(for_stmt.iterable())?;
+		let iterable = self.visit_iterable(for_stmt.iterable(), for_stmt.key().is_some())?;
+		self.loops.borrow_mut().push(LoopState::new(iterable.len()));
+		let result = self.visit_for_items(for_stmt, iterable);
+		self.loops.borrow_mut().pop();
+		result
+	}
+
+	fn visit_for_items(&self, for_stmt: &For, iterable: ForItems) -> StmtResult {
 		let body = for_stmt.body();
-		let mut content = String::new();
-		for e in iterable {
-			self.set_variable(&var, (&e).try_into()?)?;
+		for (i, (key, item)) in iterable.enumerate() {
+			if let Some(state) = self.loops.borrow_mut().last_mut() {
+				state.set_index(i);
+			}
+			if let Some(key_pattern) = for_stmt.key() {
+				self.bind_pattern(key_pattern, key)?;
+			}
+			self.bind_pattern(for_stmt.pattern(), item)?;
 			let (status, r) = body.accept(self)?;
-			content += r.to_string().as_str();
 			match status {
-				ExitStatus::Okay | ExitStatus::Continue => (),
-				ExitStatus::Break => break,
+				ExitStatus::Okay | ExitStatus::Continue => self.write_result(&r)?,
+				ExitStatus::Break => {
+					self.write_result(&r)?;
+					break;
+				},
 				ExitStatus::Return => match r {
 					OsmiaResult::None => break,
 					_ => return Ok((status, r)),
 				}
 			}
 		}
-		Ok((ExitStatus::Okay, content.into()))
+		Ok((ExitStatus::Okay, String::new().into()))
 	}
 
 	fn visit_print(&self, s: &Print) -> StmtResult {
-		println!("{}", self.visit_expr(s.expr())?);
+		let value = self.visit_expr(s.expr())?.to_string();
+		match &self.print_sink {
+			PrintSink::Stdout => println!("{}", value),
+			PrintSink::Stderr => eprintln!("{}", value),
+			PrintSink::Buffer => self.printed.borrow_mut().push(value),
+			PrintSink::Callback(callback) => callback(&value),
+		}
 		Ok((ExitStatus::Okay, OsmiaResult::None))
 	}
 
@@ -209,13 +437,81 @@ impl OsmiaInterpreter<'_> {
 		)?;
 		Ok((ExitStatus::Okay, OsmiaResult::None))
 	}
+
+	fn visit_capture(&self, capture: &Capture) -> StmtResult {
+		let var = Self::var_arr_to_ctx_variable(self.visit_variable(capture.variable())?.vec())?;
+		let ((status, r), output) = self.capture(|| {
+			let (status, r) = capture.body().accept(self)?;
+			match status {
+				ExitStatus::Return => Ok((status, r)),
+				_ => self.write_result(&r).map(|_| (status, OsmiaResult::None)),
+			}
+		})?;
+		self.set_variable(&var, (&Expr::Str(output.clone())).try_into()?)?;
+		let mut captured = self.captured.borrow_mut();
+		captured.retain(|(keys, _)| *keys != var);
+		captured.push((var, output));
+		Ok((status, r))
+	}
+
+	fn visit_file_block(&self, file: &FileBlock) -> StmtResult {
+		let path = match file.path().accept(self)? {
+			Expr::Str(s) if s.is_empty() => return Err("The path of a file cannot be empty".into()),
+			Expr::Str(s) => s,
+			e => return Err(format!("The path of a file must be a string, got {}", e).into()),
+		};
+		let ((status, r), output) = self.capture(|| {
+			let (status, r) = file.body().accept(self)?;
+			match status {
+				ExitStatus::Return => Ok((status, r)),
+				_ => self.write_result(&r).map(|_| (status, OsmiaResult::None)),
+			}
+		})?;
+		self.files.borrow_mut().entry(path).or_default().push_str(&output);
+		Ok((status, r))
+	}
+
+	fn visit_include(&self, include: &Include) -> StmtResult {
+		let name = match include.name().accept(self)? {
+			Expr::Str(s) => s,
+			e => return Err(format!("The name of a template must be a string, got {}", e).into()),
+		};
+		if self.loader.is_none() {
+			return Err(format!("Cannot include {:?} without a template loader", name).into());
+		}
+		if self.includes.borrow().contains(&name) {
+			let mut cycle = self.includes.borrow().clone();
+			cycle.push(name);
+			return Err(format!("Include cycle detected: {}", cycle.join(" -> ")).into());
+		}
+		let load = || TemplateResolver::new(self.loader)
+			.with_syntax(self.syntax.clone())
+			.load(&name);
+		let template = match self.template_cache {
+			Some(cache) => cache.get_or_load(&name, load)?,
+			None => Rc::new(load()?),
+		};
+		let (code, template) = template.as_ref();
+		self.includes.borrow_mut().push(name);
+		let result = template.accept(self).map_err(|e| e.with_code_trace(code));
+		self.includes.borrow_mut().pop();
+		match result? {
+			(ExitStatus::Break, _) | (ExitStatus::Continue, _) => Err(
+				"Cannot break or continue out of an included template".into()
+			),
+			(_, r) => Ok((ExitStatus::Okay, r)),
+		}
+	}
 }
 
 impl OsmiaInterpreter<'_> {
 	fn visit_binary(&self, binary: &Binary) -> ExprResult {
+		if let BinaryOp::NullCoalesce = binary.operator() {
+			return self.visit_null_coalescing(binary);
+		}
 		let left = binary.left().accept(self)?;
 		let right = binary.right().accept(self)?;
-		match binary.operator() {
+		let result = match binary.operator() {
 			BinaryOp::Plus => left + right,
 			BinaryOp::Minus => left - right,
 			BinaryOp::Mult => left * right,
@@ -234,6 +530,35 @@ impl OsmiaInterpreter<'_> {
 			BinaryOp::BitShiftRight => left >> right,
 			BinaryOp::And => Ok(Expr::Bool(left.to_bool() && right.to_bool())),
 			BinaryOp::Or => Ok(Expr::Bool(left.to_bool() || right.to_bool())),
+			BinaryOp::Range => Self::range(&left, &right, Range::new),
+			BinaryOp::RangeInclusive => Self::range(&left, &right, Range::new_inclusive),
+			BinaryOp::NullCoalesce => unreachable!(),
+		};
+		self.check_collection(result?)
+	}
+
+	/// The right side is only evaluated if the left one is null or a variable not
+	/// found in the context. Any other error of the left side is propagated.
+	fn visit_null_coalescing(&self, binary: &Binary) -> ExprResult {
+		match self.null_coalescing_operand(binary.left())? {
+			Expr::Null => binary.right().accept(self),
+			left => Ok(left),
+		}
+	}
+
+	/// Evaluates the left side of `??`, where a variable with a key that does not
+	/// exist is null, also inside chains like `a ?? b ?? c`.
+	fn null_coalescing_operand(&self, expr: &Expr) -> ExprResult {
+		match expr {
+			Expr::Grouping(g) => self.null_coalescing_operand(g.expr()),
+			Expr::Variable(v) => self.get_visited_variable(&self.visit_variable(v)?, true),
+			Expr::Binary(b) if matches!(b.operator(), BinaryOp::NullCoalesce) => {
+				match self.null_coalescing_operand(b.left())? {
+					Expr::Null => self.null_coalescing_operand(b.right()),
+					left => Ok(left),
+				}
+			},
+			expr => expr.accept(self),
 		}
 	}
 
@@ -249,12 +574,23 @@ impl OsmiaInterpreter<'_> {
 		}
 	}
 
+	fn visit_ternary(&self, ternary: &Ternary) -> ExprResult {
+		self.ternary_branch(ternary)?.accept(self)
+	}
+
+	fn ternary_branch<'b>(&self, ternary: &'b Ternary) -> Result<&'b Expr, OsmiaError> {
+		Ok(match ternary.condition().accept(self)?.to_bool() {
+			true => ternary.then(),
+			false => ternary.otherwise(),
+		})
+	}
+
 	fn visit_array(&self, arr: &Array) -> ExprResult {
 		let mut new_arr = Vec::new();
 		for e in arr.iter() {
 			new_arr.push(e.accept(self)?);
 		}
-		Ok(Expr::Array(new_arr.into()))
+		self.check_collection(Expr::Array(new_arr.into()))
 	}
 
 	fn visit_object(&self, obj: &Object) -> ExprResult {
@@ -265,7 +601,7 @@ impl OsmiaInterpreter<'_> {
 				for (e, v) in items {
 					new_obj.push((self.visit_expr(&e)?, self.visit_expr(&v)?));
 				}
-				Ok(Expr::Object(Object::new_hash(new_obj)?))
+				self.check_collection(Expr::Object(Object::new_hash(new_obj)?))
 			},
 			Object::Hash(h) => Ok(self.visit_object(&Object::new_code(h.entries()))?),
 		}
@@ -296,9 +632,69 @@ impl OsmiaInterpreter<'_> {
 	}
 
 	fn visit_call(&self, call: &Call) -> ExprResult {
-		match call.callee().accept(self)? {
-			Expr::Callable(c) => self.visit_expr(&self.make_call(&c, call.args())?),
-			e => Err(format!("Expression {} is not callable", e)),
+		Ok(match self.call_result(call)? {
+			OsmiaResult::Expr(e) => e,
+			OsmiaResult::OsmiaOutput(s) => Expr::Str(s),
+			OsmiaResult::None => Expr::Null,
+		})
+	}
+
+	/// Calls the function, keeping apart the output rendered by the functions of the template.
+	fn call_result(&self, call: &Call) -> Result<OsmiaResult, OsmiaError> {
+		let callable = match call.callee().accept(self)? {
+			Expr::Callable(c) => c,
+			e => return Err(format!("Expression {} is not callable", e).into()),
+		};
+		let depth = self.call_depth.get() + 1;
+		if let Some(max) = self.limits.call_depth() {
+			if depth > max {
+				return Err(OsmiaError::limit(LimitKind::CallDepth, max));
+			}
+		}
+		self.call_depth.set(depth);
+		let result = self.make_call(&callable, call.args()).and_then(|r| match r {
+			OsmiaResult::Expr(body) => Ok(self.check_collection(self.visit_expr(&body)?)?.into()),
+			r => Ok(r),
+		});
+		self.call_depth.set(depth - 1);
+		result
+	}
+
+	/// Evaluates an expression to be printed, deciding if it has to be escaped.
+	fn visit_output(&self, expr: &Expr) -> Result<OsmiaResult, OsmiaError> {
+		let result = match expr {
+			Expr::Grouping(g) => self.visit_output(g.expr()),
+			Expr::Ternary(t) => self.visit_output(self.ternary_branch(t)?),
+			Expr::Call(c) => self.call_result(c),
+			Expr::MethodCall(m) if Self::is_safe_call(m) => Ok(OsmiaResult::OsmiaOutput(
+				self.visit_expr(m.obj.as_ref())?.to_string()
+			)),
+			Expr::Variable(v) => Ok(self.captured_output(v, self.visit_expr(expr)?)),
+			_ => Ok(OsmiaResult::Expr(self.visit_expr(expr)?)),
+		};
+		result.map_err(|e| e.or_location(expr.location()))
+	}
+
+	/// The output stored by `capture` has already been escaped, so it is printed
+	/// as it is while the variable keeps it.
+	fn captured_output(&self, variable: &Variable, value: Expr) -> OsmiaResult {
+		let keys = variable.vec().iter().map(|k| match k {
+			JsonTreeKeyExpr::JsonTreeKey(k) => Some(k.clone()),
+			JsonTreeKeyExpr::Expr(_) => None,
+		}).collect::<Option<Vec<_>>>();
+		match (keys, value) {
+			(Some(keys), Expr::Str(s)) if self.captured.borrow().iter().any(
+				|(k, output)| *k == keys && *output == s
+			) => OsmiaResult::OsmiaOutput(s),
+			(_, value) => OsmiaResult::Expr(value),
+		}
+	}
+
+	fn is_safe_call(m: &MethodCall) -> bool {
+		let safe = JsonTreeKeyExpr::JsonTreeKey(JsonTreeKey::Key(SAFE_METHOD.into()));
+		m.call.args().is_empty() && match m.call.callee() {
+			Expr::Variable(v) => v.vec().as_slice() == [safe],
+			_ => false,
 		}
 	}
 
@@ -322,29 +718,36 @@ impl OsmiaInterpreter<'_> {
 		self.visit_call(&call)
 	}
 
-	fn make_call(&self, call: &Callable, args: &Vec<Expr>) -> ExprResult {
+	fn make_call(&self, call: &Callable, args: &Vec<Expr>) -> Result<OsmiaResult, OsmiaError> {
 		let args = self.setup_callable_args(args, call)?;
-		let expr: Expr = match call {
-			Callable::Builtin(_) | Callable::Lambda(_) => call.call(self, &args)?,
+		let result = match call {
+			Callable::Builtin(_) | Callable::Lambda(_) => call.call(self, &args)?.into(),
 			Callable::Function(_) => {
+				let loops = self.loops.take();
 				self.ctx.borrow_mut().begin_scope();
-				let ft_body = call.call_stmt(self, &args)?;
-				let (status, r) = self.visit_stmt(&ft_body)?;
-				let result = match status {
-					ExitStatus::Continue | ExitStatus::Break => return Err(format!(
-						"Cannot break or continue out of a function"
-					)),
-					ExitStatus::Okay | ExitStatus::Return => match r {
-						OsmiaResult::None => Expr::Null,
-						OsmiaResult::Expr(e) => e,
-						OsmiaResult::OsmiaOutput(s) => Expr::Str(s),
-					}
-				};
+				let result = self.call_function(call, &args);
 				self.ctx.borrow_mut().end_scope();
-				result
+				self.loops.replace(loops);
+				result?
 			}
 		};
-		Ok(expr)
+		Ok(result)
+	}
+
+	/// Runs the body of a function. The `loop` of the caller is not visible in it.
+	fn call_function(&self, call: &Callable, args: &Vec<Expr>) -> Result<OsmiaResult, OsmiaError> {
+		let ft_body = call.call_stmt(self, args)?;
+		let ((status, r), output) = self.capture(|| self.visit_stmt(&ft_body))?;
+		match status {
+			ExitStatus::Continue | ExitStatus::Break => Err(
+				"Cannot break or continue out of a function".into()
+			),
+			ExitStatus::Okay | ExitStatus::Return => Ok(match r {
+				OsmiaResult::None => Expr::Null.into(),
+				OsmiaResult::Expr(e) => e.into(),
+				OsmiaResult::OsmiaOutput(s) => (output + &s).into(),
+			}),
+		}
 	}
 
 	fn setup_callable_args(&self, args: &Vec<Expr>, call: &Callable) -> Result<Vec<Expr>, OsmiaError> {
@@ -383,14 +786,17 @@ impl OsmiaInterpreter<'_> {
 				(_, None) => break,
 				(arg, Some(p)) => match (arg, p) {
 					(_, FunctionParam::Spread(_)) => {
-						arguments.push(Expr::Array(args[i..].to_vec().into()));
+						let rest = args.get(i..).unwrap_or_default().iter()
+							.map(|arg| self.visit_expr(arg))
+							.collect::<Result<Vec<Expr>, OsmiaError>>()?;
+						arguments.push(Expr::Array(rest.into()));
 						break;
 					},
 					(Some(arg), _) => arguments.push(self.visit_expr(arg)?),
 					(None, _) => match p {
 						FunctionParam::Param(p, None) => return Err(format!(
 							"Missing argument: {}", p
-						)),
+						).into()),
 						FunctionParam::Param(_, Some(d)) => arguments.push(self.visit_expr(d)?),
 						FunctionParam::Spread(_) => unreachable!(),
 					}
@@ -401,29 +807,74 @@ impl OsmiaInterpreter<'_> {
 		Ok(arguments)
 	}
 
-	fn visit_iterable(&self, iterable: &Expr) -> Result<Vec<Expr>, OsmiaError> {
-		let iterable: Expr = match iterable {
-			Expr::Array(arr) => self.visit_array(arr)?,
-			Expr::Object(obj) => self.visit_object(obj)?,
-			Expr::Variable(v) => match self.get_variable(v)? {
-				Expr::Array(a) => Expr::Array(a),
-				Expr::Object(o) => Expr::Object(o),
-				_ => return Err(format!("Variable {:?} is not iterable", v)),
-			},
-			_ => return Err(format!("Cannot iterate over: {:?}", iterable)),
-		};
-		Ok(match iterable {
-			Expr::Array(arr) => (&arr).into(),
-			Expr::Object(obj) => obj.entries().iter().map(|(k, v)| {
-				Ok(Expr::Object(Object::new_hash(vec![
-					(Expr::Str("key".into()), k.clone()),
-					(Expr::Str("value".into()), v.clone()),
-				])?))
-			}).collect::<Result<Vec<Expr>, OsmiaError>>()?,
-			_ => unreachable!(),
+	/// Pairs of index (or key) and item to iterate over, from any expression
+	/// resulting in an array, object, string or range. Ranges are not materialized.
+	/// Without keys, the items of an object are `{"key": key, "value": value}` objects.
+	fn visit_iterable(&self, iterable: &Expr, with_keys: bool) -> Result<ForItems, OsmiaError> {
+		Ok(match self.visit_expr(iterable)? {
+			Expr::Array(arr) => Box::new(arr.entries().into_iter()),
+			Expr::Str(s) => Box::new(s.chars()
+				.enumerate()
+				.map(|(i, c)| (Expr::Int(i as i64), Expr::Str(c.to_string())))
+				.collect::<Vec<(Expr, Expr)>>()
+				.into_iter()
+			),
+			Expr::Range(r) => Box::new(r.iter().enumerate()
+				.map(|(i, n)| (Expr::Int(i as i64), Expr::Int(n)))
+			),
+			Expr::Object(obj) if with_keys => Box::new(obj.entries().into_iter()),
+			Expr::Object(obj) => Box::new(obj.entries().into_iter().map(|(k, v)| {
+				Ok((k.clone(), Expr::Object(Object::new_hash(vec![
+					(Expr::Str("key".into()), k),
+					(Expr::Str("value".into()), v),
+				])?)))
+			}).collect::<Result<Vec<(Expr, Expr)>, OsmiaError>>()?.into_iter()),
+			value => return Err(format!(
+				"Cannot iterate over a value of type {}: {}", value.r#type(), value
+			).into()),
 		})
 	}
 
+	fn bind_pattern(&self, pattern: &Pattern, value: Expr) -> Result<(), OsmiaError> {
+		match pattern {
+			Pattern::Variable(var) => {
+				let var = Self::var_arr_to_ctx_variable(self.visit_variable(var)?.vec())?;
+				self.set_variable(&var, (&value).try_into()?)
+			},
+			Pattern::Object(entries) => {
+				let obj = match &value {
+					Expr::Object(obj) => obj,
+					_ => return Err(format!(
+						"Cannot destructure a {} as an object: {}", value.r#type(), value
+					).into()),
+				};
+				for (key, pattern) in entries {
+					let item = obj.get(key).cloned().ok_or_else(|| OsmiaError::runtime(format!(
+						"Cannot destructure the key {} from the object {}", key, value
+					)))?;
+					self.bind_pattern(pattern, item)?;
+				}
+				Ok(())
+			},
+			Pattern::Array(patterns) => {
+				let arr = match &value {
+					Expr::Array(arr) if arr.len() == patterns.len() => arr,
+					Expr::Array(arr) => return Err(format!(
+						"Cannot destructure an array of {} items into {} variables: {}",
+						arr.len(), patterns.len(), value
+					).into()),
+					_ => return Err(format!(
+						"Cannot destructure a {} as an array: {}", value.r#type(), value
+					).into()),
+				};
+				for (pattern, item) in patterns.iter().zip(arr.iter()) {
+					self.bind_pattern(pattern, item.clone())?;
+				}
+				Ok(())
+			},
+		}
+	}
+
 	fn visit_variable(&self, variable: &Variable) -> Result<Variable, OsmiaError> {
 		let mut arr: Vec<JsonTreeKeyExpr> = Vec::with_capacity(variable.vec().len());
 		for v in variable.vec() {
@@ -432,7 +883,7 @@ impl OsmiaInterpreter<'_> {
 				k => arr.push(k.clone()),
 			}
 		}
-		Ok(Variable::from_vec(arr))
+		Ok(Variable::from_vec(arr).with_safe_keys_of(variable))
 	}
 
 	fn var_arr_to_ctx_variable(
@@ -447,11 +898,11 @@ impl OsmiaInterpreter<'_> {
 						Expr::Str(s) => JsonTreeKey::Key(s.into()),
 						Expr::Int(i) => {
 							if *i < 0 {
-								return Err(format!("Invalid variable index: {:?}", e));
+								return Err(format!("Invalid variable index: {:?}", e).into());
 							}
 							JsonTreeKey::Index(*i as usize)
 						},
-						_ => return Err(format!("Invalid variable key: {:?}", e)),
+						_ => return Err(format!("Invalid variable key: {:?}", e).into()),
 					};
 					variable_keys.push(key);
 				},
@@ -462,22 +913,71 @@ impl OsmiaInterpreter<'_> {
 }
 
 impl OsmiaInterpreter<'_> {
-	fn get_variable<'a>(&self, variable: &Variable) -> ExprResult {
-		let variable = self.visit_variable(variable)?;
+	fn get_variable(&self, variable: &Variable) -> ExprResult {
+		self.get_visited_variable(&self.visit_variable(variable)?, false)
+	}
+
+	/// Gets a variable whose keys have already been evaluated. With
+	/// `missing_as_null`, a key that does not exist results in null.
+	fn get_visited_variable(&self, variable: &Variable, missing_as_null: bool) -> ExprResult {
+		let last_safe_key = variable.safe_keys().iter().max().copied();
 		match variable.vec().get(0) {
-			Some(JsonTreeKeyExpr::JsonTreeKey(_)) => self.get_variable_from_ctx(&self.ctx.borrow(), &variable.vec()),
-			Some(JsonTreeKeyExpr::Expr(e)) => {
-				let context = Ctx::from(e.try_into()?);
-				let keys: Vec<JsonTreeKeyExpr> = variable.vec().iter().skip(1).map(|k| k.clone()).collect();
-				self.get_variable_from_ctx(&context, &keys)
-			},
+			Some(JsonTreeKeyExpr::JsonTreeKey(key)) if self.is_loop_variable(key) => self.get_variable_from_expr(
+				&self.loop_variable()?, variable, last_safe_key, missing_as_null
+			),
+			Some(JsonTreeKeyExpr::JsonTreeKey(_)) => self.get_variable_from_ctx(
+				&self.ctx.borrow(), variable.vec(), last_safe_key, missing_as_null
+			),
+			Some(JsonTreeKeyExpr::Expr(e)) => self.get_variable_from_expr(
+				e, variable, last_safe_key, missing_as_null
+			),
 			None => unreachable!()
 		}
 	}
 
-	fn get_variable_from_ctx(&self, ctx: &Ctx, variable: &Vec<JsonTreeKeyExpr>) -> ExprResult {
+	/// Gets the keys of the variable, except the first one, from the given expression.
+	fn get_variable_from_expr(
+		&self,
+		expr: &Expr,
+		variable: &Variable,
+		last_safe_key: Option<usize>,
+		missing_as_null: bool,
+	) -> ExprResult {
+		let context = Ctx::from(expr.try_into()?);
+		let keys: Vec<JsonTreeKeyExpr> = variable.vec().iter().skip(1).cloned().collect();
+		self.get_variable_from_ctx(&context, &keys, last_safe_key.map(|i| i - 1), missing_as_null)
+	}
+
+	/// Inside a `for`, `loop` is its state unless a variable with that name
+	/// shadows it.
+	fn is_loop_variable(&self, key: &JsonTreeKey<String>) -> bool {
+		!self.loops.borrow().is_empty()
+			&& *key == JsonTreeKey::Key(LOOP_VARIABLE.into())
+			&& self.ctx.borrow().get(&vec![key.clone()]).is_err()
+	}
+
+	/// The `loop` object of the innermost `for`, linked to the enclosing ones.
+	fn loop_variable(&self) -> ExprResult {
+		self.loops.borrow().iter()
+			.try_fold(Expr::Null, |outer, state| state.to_expr(outer))
+	}
+
+	/// With safe navigation, the variable is null if any key up to the last
+	/// safe one cannot be reached.
+	fn get_variable_from_ctx(
+		&self,
+		ctx: &Ctx,
+		variable: &Vec<JsonTreeKeyExpr>,
+		last_safe_key: Option<usize>,
+		missing_as_null: bool,
+	) -> ExprResult {
 		let keys = Self::var_arr_to_ctx_variable(&variable)?;
-		Ok(ctx.get(&keys)?.try_into()?)
+		match (ctx.get(&keys), last_safe_key) {
+			(Ok(value), _) => Ok(value.try_into()?),
+			(Err(_), Some(i)) if ctx.get(&keys[..=i].to_vec()).is_err() => Ok(Expr::Null),
+			(Err(_), _) if missing_as_null && ctx.get_optional(&keys)?.is_none() => Ok(Expr::Null),
+			(Err(e), _) => Err(e),
+		}
 	}
 
 	fn set_variable<'a>(
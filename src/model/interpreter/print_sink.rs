@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+/// Destination of the values printed with `{{print value}}`.
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use osmia::{Osmia, PrintSink};
+///
+/// let mut osmia = Osmia::default().with_print_sink(PrintSink::Buffer);
+/// let (output, printed) = osmia.run_code_with_prints("a{{print 1 + 1}}b").unwrap();
+/// assert_eq!(output, "ab");
+/// assert_eq!(printed, vec!["2"]);
+///
+/// let logs = Arc::new(Mutex::new(Vec::new()));
+/// let sink_logs = logs.clone();
+/// osmia.set_print_sink(PrintSink::callback(move |line| sink_logs.lock().unwrap().push(line.to_string())));
+/// osmia.run_code("{{print \"debug\"}}").unwrap();
+/// assert_eq!(*logs.lock().unwrap(), vec!["debug"]);
+/// ```
+#[derive(Clone, Default)]
+pub enum PrintSink {
+	/// Prints every value in its own line of the standard output.
+	#[default]
+	Stdout,
+	/// Prints every value in its own line of the standard error.
+	Stderr,
+	/// Keeps the values in memory, returned by the `*_with_prints` methods, like
+	/// [Osmia::run_code_with_prints](crate::Osmia::run_code_with_prints),
+	/// [Osmia::render_to_with_prints](crate::Osmia::render_to_with_prints) and
+	/// [Template::render_with_prints](crate::Template::render_with_prints).
+	///
+	/// Any other method, like `run_code`, `render` or `render_to`, discards the
+	/// values, as do all of them when the rendering fails.
+	Buffer,
+	/// Sends every value to the given function.
+	Callback(Arc<dyn Fn(&str) + Send + Sync>),
+}
+
+impl PrintSink {
+	pub fn callback(callback: impl Fn(&str) + Send + Sync + 'static) -> Self {
+		Self::Callback(Arc::new(callback))
+	}
+}
+
+impl std::fmt::Debug for PrintSink {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			PrintSink::Stdout => write!(f, "Stdout"),
+			PrintSink::Stderr => write!(f, "Stderr"),
+			PrintSink::Buffer => write!(f, "Buffer"),
+			PrintSink::Callback(_) => write!(f, "Callback"),
+		}
+	}
+}
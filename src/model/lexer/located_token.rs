@@ -0,0 +1,52 @@
+use super::Token;
+use crate::model::error::Location;
+
+/// [Token] together with the position in the code where it starts.
+#[derive(Debug, Clone)]
+pub struct LocatedToken {
+	token: Token,
+	location: Location,
+}
+
+impl LocatedToken {
+	pub fn new(token: Token, location: Location) -> Self {
+		Self { token, location }
+	}
+
+	pub fn token(&self) -> &Token {
+		&self.token
+	}
+
+	pub fn location(&self) -> Location {
+		self.location
+	}
+
+	pub fn set_token(&mut self, token: Token) {
+		self.token = token;
+	}
+}
+
+impl From<Token> for LocatedToken {
+	fn from(token: Token) -> Self {
+		Self::new(token, Location::new(0, 1, 1))
+	}
+}
+
+impl From<LocatedToken> for Token {
+	fn from(token: LocatedToken) -> Self {
+		token.token
+	}
+}
+
+/// Tokens are compared without taking into account their location.
+impl PartialEq for LocatedToken {
+	fn eq(&self, other: &Self) -> bool {
+		self.token == other.token
+	}
+}
+
+impl PartialEq<Token> for LocatedToken {
+	fn eq(&self, other: &Token) -> bool {
+		&self.token == other
+	}
+}
@@ -1,9 +1,13 @@
 mod lexer;
+mod located_token;
 mod osmia_lexer;
 mod token;
 mod osmia_lexer_scanner;
+mod syntax;
 
 pub use lexer::Lexer;
+pub use located_token::LocatedToken;
 pub use osmia_lexer::OsmiaLexer;
 pub use token::Token;
 pub use osmia_lexer_scanner::OsmiaLexerScanner;
+pub use syntax::Syntax;
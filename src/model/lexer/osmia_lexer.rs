@@ -1,24 +1,34 @@
 use super::{
 	Lexer,
-	OsmiaLexerScanner
+	OsmiaLexerScanner,
+	Syntax,
 };
 use crate::LexerCode;
 use crate::OsmiaError;
 use crate::model::lexer::Token;
 
-pub struct OsmiaLexer {}
+pub struct OsmiaLexer {
+	syntax: Syntax,
+}
 
 impl OsmiaLexer {
 	pub fn new() -> Self {
-		Self {}
+		Self {
+			syntax: Syntax::default(),
+		}
+	}
+
+	pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+		self.syntax = syntax;
+		self
 	}
 }
 
 impl Lexer<LexerCode, OsmiaError> for OsmiaLexer {
 	fn lex(&self, code: &str) -> Result<LexerCode, OsmiaError> {
-		let scanner = OsmiaLexerScanner::new(code);
+		let scanner = OsmiaLexerScanner::new(code).with_syntax(self.syntax.clone());
 		match scanner.scan() {
-			Err(err) => Err(format!("Lexer error: {}", err)),
+			Err(err) => Err(err),
 			Ok(tokens) => Ok(Self::clean_tokens(tokens)),
 		}
 	}
@@ -32,12 +42,13 @@ impl OsmiaLexer {
 			line_end = i;
 			i = Self::find_start_line(&tokens, line_end);
 			if !Self::is_printable_line(&tokens, i, line_end) {
-				if matches!(tokens[line_end], Token::NewLine) {
-					tokens[line_end] = Token::NewLineNonPrintable;
+				if matches!(tokens[line_end].token(), Token::NewLine) {
+					tokens[line_end].set_token(Token::NewLineNonPrintable);
 				}
 				let mut j = i;
-				while j < line_end && Self::is_whitespace_token(&tokens[j]) {
-					tokens[j] = Token::NonPrintable(tokens[j].as_raw_str().unwrap().into());
+				while j < line_end && Self::is_whitespace_token(tokens[j].token()) {
+					let raw: String = tokens[j].token().as_raw_str().unwrap().into();
+					tokens[j].set_token(Token::NonPrintable(raw));
 					j += 1;
 				}
 			}
@@ -54,10 +65,10 @@ impl OsmiaLexer {
 			return 0;
 		}
 		let mut i = end_line - 1;
-		while i > 0 && !matches!(tokens[i], Token::NewLine) {
+		while i > 0 && !matches!(tokens[i].token(), Token::NewLine) {
 			i -= 1;
 		}
-		if matches!(tokens[i], Token::NewLine) && i < end_line {
+		if matches!(tokens[i].token(), Token::NewLine) && i < end_line {
 			i += 1;
 		}
 		return i;
@@ -68,7 +79,7 @@ impl OsmiaLexer {
 			return true;
 		}
 		// indenting
-		while start < end && Self::is_whitespace_token(&tokens[start]) {
+		while start < end && Self::is_whitespace_token(tokens[start].token()) {
 			start += 1;
 		}
 		// non-printable stmts
@@ -77,28 +88,30 @@ impl OsmiaLexer {
 				None => return true,
 				Some(p) => p
 			};
-			assert!(start == end || matches!(tokens[start], Token::StmtEnd));
+			assert!(start == end || matches!(tokens[start].token(), Token::StmtEnd));
 			start += 1;
 		}
 		false
 	}
 
 	fn walk_non_printable_stmt(tokens: &LexerCode, mut start: usize, end: usize) -> Option<(usize, usize)> {
-		if !matches!(tokens[start], Token::StmtStart) {
+		if !matches!(tokens[start].token(), Token::StmtStart) {
 			return None;
 		}
 		start += 1;
-		while start < end && matches!(tokens[start], Token::Whitespace) {
+		while start < end && matches!(tokens[start].token(), Token::Whitespace) {
 			start += 1;
 		}
-		match tokens[start] {
+		match tokens[start].token() {
 			Token::Print | Token::Comment | Token::Function | Token::Return => (),
+			Token::Extends | Token::Block | Token::RawBlock | Token::EndRawBlock => (),
 			Token::If | Token::ElseIf | Token::Else | Token::Fi => (),
 			Token::While | Token::For | Token::Continue | Token::Break | Token::Done => (),
+			Token::Capture | Token::FileBlock => (),
 			_ => {
 				let mut is_assignment = true;
-				while start < end && !matches!(tokens[start], Token::StmtEnd) {
-					if matches!(tokens[start], Token::Assign) {
+				while start < end && !matches!(tokens[start].token(), Token::StmtEnd) {
+					if matches!(tokens[start].token(), Token::Assign) {
 						is_assignment = false;
 					}
 					start += 1;
@@ -106,7 +119,7 @@ impl OsmiaLexer {
 				return if is_assignment { None } else { Some((start, end)) };
 			}
 		}
-		while start < end && !matches!(tokens[start], Token::StmtEnd) {
+		while start < end && !matches!(tokens[start].token(), Token::StmtEnd) {
 			start += 1;
 		}
 		Some((start, end))
@@ -1,18 +1,27 @@
-use crate::model::lexer::Token;
-use crate::utils::code_trace;
-use crate::LexerCode;
-use crate::constants::{
-	START_DELIMITER,
-	END_DELIMITER
+use crate::model::lexer::{
+	Token,
+	LocatedToken,
+	Syntax,
+};
+use crate::model::error::{
+	ErrorInfo,
+	Location,
+};
+use crate::types::{
+	LexerCode,
+	OsmiaError,
 };
 
 pub struct OsmiaLexerScanner<'a> {
 	code: &'a [u8],
 	index: usize,
-	current_line: usize,
+	line_starts: Vec<usize>,
 	tokens: LexerCode,
 	in_stmt: bool,
-	obj_depth: usize
+	obj_depth: usize,
+	array_depth: usize,
+	parent_depth: usize,
+	syntax: Syntax,
 }
 
 impl<'a> OsmiaLexerScanner<'a> {
@@ -20,21 +29,38 @@ impl<'a> OsmiaLexerScanner<'a> {
 		Self {
 			code: code.as_bytes(),
 			index: 0,
-			current_line: 0,
+			line_starts: Self::find_line_starts(code),
 			tokens: Vec::new(),
 			in_stmt: false,
-			obj_depth: 0
+			obj_depth: 0,
+			array_depth: 0,
+			parent_depth: 0,
+			syntax: Syntax::default(),
 		}
 	}
 
-	pub fn scan(mut self) -> Result<LexerCode, String> {
+	pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+		self.syntax = syntax;
+		self
+	}
+
+	pub fn scan(mut self) -> Result<LexerCode, OsmiaError> {
+		self.syntax.validate()?;
 		while self.code_left() {
-			while !self.is_match(START_DELIMITER) && self.code_left() {
+			while !self.is_start_delimiter() && self.code_left() {
 				self.consume_raw();
 			}
-			if self.is_match(START_DELIMITER) {
+			if self.is_keyword_stmt(Token::RawBlock).is_some() {
+				self.consume_raw_block()?;
+			} else if self.is_keyword_stmt(Token::EndRawBlock).is_some() {
+				return Err(self.error(format!(
+					"Unexpected '{}{:?}{}' without an opening '{}{:?}{}'",
+					self.syntax.start(), Token::EndRawBlock, self.syntax.end(),
+					self.syntax.start(), Token::RawBlock, self.syntax.end()
+				)));
+			} else if self.is_start_delimiter() {
 				self.consume_start_delimiter();
-				if self.consume("#") {
+				if self.consume_comment_marker() {
 					self.consume_comment();
 					self.consume_end_delimiter();
 				}
@@ -46,14 +72,14 @@ impl<'a> OsmiaLexerScanner<'a> {
 		if self.in_stmt {
 			return Err(self.error(format!(
 				"Unexpected end of statement. Expected '{}'",
-				END_DELIMITER
+				self.syntax.end()
 			)));
 		}
-		self.tokens.push(Token::Eof);
+		self.push(Token::Eof, self.current_index());
 		Ok(self.tokens)
 	}
 	
-	pub fn scan_stmt(mut self) -> Result<LexerCode, String> {
+	pub fn scan_stmt(mut self) -> Result<LexerCode, OsmiaError> {
 		self.in_stmt = true;
 		self.consume_stmt_body()?;
 		Ok(self.tokens)
@@ -61,24 +87,31 @@ impl<'a> OsmiaLexerScanner<'a> {
 }
 
 impl<'a> OsmiaLexerScanner<'a> {
-	fn error(&self, msg: String) -> String {
-		let code_str: String = self.code.iter().map(|b| *b as char).collect();
-		if cfg!(debug_assertions) {
-			code_trace(
-				&code_str, self.current_index(),
-				&format!(
-					"Line {}: {}\nTokens: {:?}",
-					self.current_line, msg,
-					self.tokens
-				)
-			)
-		}
-		else {
-			code_trace(
-				&code_str, self.current_index(),
-				&format!("Line {}: {}", self.current_line, msg)
-			)
-		}
+	fn find_line_starts(code: &str) -> Vec<usize> {
+		let mut line_starts = vec![0];
+		line_starts.extend(code.match_indices('\n').map(|(i, _)| i + 1));
+		line_starts
+	}
+
+	fn location(&self, offset: usize) -> Location {
+		let line = self.line_starts.partition_point(|start| *start <= offset);
+		Location::new(offset, line, offset - self.line_starts[line - 1] + 1)
+	}
+
+	fn push(&mut self, token: Token, start: usize) {
+		let location = self.location(start);
+		self.tokens.push(LocatedToken::new(token, location));
+	}
+
+	fn error(&self, msg: String) -> OsmiaError {
+		let code_str = String::from_utf8_lossy(self.code);
+		let mut info = ErrorInfo::new(msg)
+			.with_location(self.location(self.current_index()))
+			.with_code_trace(&code_str);
+		if self.code_left() {
+			info = info.with_culprit((self.current() as char).to_string());
+		}
+		OsmiaError::Lexer(info)
 	}
 
 	fn code_left(&self) -> bool {
@@ -111,8 +144,9 @@ impl<'a> OsmiaLexerScanner<'a> {
 
 	fn consume_in_order(&mut self, options: Vec<(&str, Token)>) -> bool {
 		for (lex, token) in options {
+			let start = self.current_index();
 			if self.consume(lex) {
-				self.tokens.push(token);
+				self.push(token, start);
 				return true;
 			}
 		}
@@ -120,13 +154,17 @@ impl<'a> OsmiaLexerScanner<'a> {
 	}
 
 	fn is_match(&self, expected: &str) -> bool {
+		self.is_match_at(self.current_index(), expected)
+	}
+
+	fn is_match_at(&self, index: usize, expected: &str) -> bool {
 		let b_expected = expected.as_bytes();
 		let mut i = 0;
-		if self.current_index() + b_expected.len() > self.code.len() {
+		if index + b_expected.len() > self.code.len() {
 			return false;
 		}
 		while i < b_expected.len() {
-			if self.code[self.current_index() + i] != b_expected[i] {
+			if self.code[index + i] != b_expected[i] {
 				return false;
 			}
 			i += 1;
@@ -157,19 +195,109 @@ impl<'a> OsmiaLexerScanner<'a> {
 impl<'a> OsmiaLexerScanner<'a> {
 	fn consume_raw(&mut self) {
 		let start = self.current_index();
-		while self.code_left() && !self.is_match(START_DELIMITER) && self.current() != b'\n' {
+		let mut content = String::new();
+		let mut piece = start;
+		while self.code_left() && !self.is_start_delimiter() && self.current() != b'\n' {
+			if self.is_escaped_backslash() {
+				self.advance();
+				content.push_str(&self.pick_string(piece, self.current_index()).unwrap());
+				self.advance();
+				piece = self.current_index();
+				continue;
+			}
+			if self.is_escaped_delimiter() {
+				content.push_str(&self.pick_string(piece, self.current_index()).unwrap());
+				self.advance();
+				piece = self.current_index();
+				self.index += self.syntax.start().len();
+				continue;
+			}
 			self.advance();
 		}
-		if let Some(content) = self.pick_non_empty_string(start, self.current_index()) {
-			self.tokens.push(Token::Raw(content));
+		content.push_str(&self.pick_string(piece, self.current_index()).unwrap());
+		if !content.is_empty() {
+			self.push(Token::Raw(content), start);
 		}
 		self.consume_new_line();
 	}
 
+	/// A backslash before the start delimiter prints the delimiter as raw text.
+	fn is_escaped_delimiter(&self) -> bool {
+		self.current() == b'\\' && self.is_match_at(self.current_index() + 1, self.syntax.start())
+	}
+
+	/// Two backslashes before the start delimiter print a single one, keeping
+	/// the statement, so `C:\\{{ dir }}` prints `C:\` followed by `dir`.
+	fn is_escaped_backslash(&self) -> bool {
+		self.current() == b'\\'
+			&& self.is_match_at(self.current_index() + 1, "\\")
+			&& self.is_match_at(self.current_index() + 2, self.syntax.start())
+	}
+
+	/// Length of the statement made only by the given keyword, like `{{raw}}`
+	/// or `{{ raw }}`, if it starts at the current position.
+	fn is_keyword_stmt(&self, keyword: Token) -> Option<usize> {
+		let keyword = format!("{:?}", keyword);
+		let mut i = self.current_index();
+		if !self.is_match_at(i, self.syntax.start()) {
+			return None;
+		}
+		i += self.syntax.start().len();
+		while i < self.code.len() && self.code[i].is_ascii_whitespace() {
+			i += 1;
+		}
+		if !self.is_match_at(i, &keyword) {
+			return None;
+		}
+		i += keyword.len();
+		while i < self.code.len() && self.code[i].is_ascii_whitespace() {
+			i += 1;
+		}
+		if !self.is_match_at(i, self.syntax.end()) {
+			return None;
+		}
+		Some(i + self.syntax.end().len() - self.current_index())
+	}
+
+	fn consume_keyword_stmt(&mut self, keyword: Token) {
+		let start = self.current_index();
+		let len = self.is_keyword_stmt(keyword.clone()).unwrap();
+		self.push(Token::StmtStart, start);
+		self.push(keyword, start + self.syntax.start().len());
+		self.index += len;
+		self.push(Token::StmtEnd, self.current_index() - self.syntax.end().len());
+	}
+
+	/// Consumes a `{{raw}}...{{endraw}}` block, keeping its body untouched.
+	fn consume_raw_block(&mut self) -> Result<(), OsmiaError> {
+		self.consume_keyword_stmt(Token::RawBlock);
+		while self.code_left() && self.is_keyword_stmt(Token::EndRawBlock).is_none() {
+			let start = self.current_index();
+			while self.code_left() && self.current() != b'\n' && self.is_keyword_stmt(Token::EndRawBlock).is_none() {
+				self.advance();
+			}
+			if let Some(content) = self.pick_non_empty_string(start, self.current_index()) {
+				self.push(Token::Raw(content), start);
+			}
+			if self.code_left() && self.current() == b'\n' {
+				self.push(Token::NewLine, self.current_index());
+				self.advance();
+			}
+		}
+		if !self.code_left() {
+			return Err(self.error(format!(
+				"Unclosed raw block. Expected '{}{:?}{}'",
+				self.syntax.start(), Token::EndRawBlock, self.syntax.end()
+			)));
+		}
+		self.consume_keyword_stmt(Token::EndRawBlock);
+		Ok(())
+	}
+
 	fn consume_new_line(&mut self) {
 		while self.code_left() && self.current() == b'\n' {
+			self.push(Token::NewLine, self.current_index());
 			self.advance();
-			self.tokens.push(Token::NewLine);
 		}
 	}
 
@@ -177,27 +305,117 @@ impl<'a> OsmiaLexerScanner<'a> {
 		let mut white_space = false;
 		while self.code_left() && self.current().is_ascii_whitespace() {
 			if self.current() != b'\n' {
-				self.advance();
 				if !white_space {
-					self.tokens.push(Token::Whitespace);
+					self.push(Token::Whitespace, self.current_index());
 					white_space = true;
 				}
+				self.advance();
 			}
 			self.consume_new_line();
 		}
 	}
 
+	fn is_start_delimiter(&self) -> bool {
+		self.is_match(self.syntax.start())
+	}
+
+	fn is_end_delimiter(&self) -> bool {
+		self.is_match(self.syntax.end())
+	}
+
+	fn consume_comment_marker(&mut self) -> bool {
+		let marker = self.syntax.comment().len();
+		if self.is_match(self.syntax.comment()) {
+			self.index += marker;
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Depth of the brackets closed by the first character of the end delimiter.
+	///
+	/// The end delimiter is not matched while they are open, so `{{ {"a": {}}}`
+	/// or `[[ a[b[0]]]]` are lexed as expected.
+	fn closing_depth(&self) -> usize {
+		match self.syntax.end().as_bytes()[0] {
+			b'}' => self.obj_depth,
+			b']' => self.array_depth,
+			b')' => self.parent_depth,
+			_ => 0,
+		}
+	}
+
+	/// `{{- ` removes the whitespace before the statement. The marker must be
+	/// followed by a whitespace so `{{-1}}` is still a negative number.
+	fn is_left_trim(&self) -> bool {
+		self.current() == b'-' && self.code.get(self.current_index() + 1).is_some_and(|c| c.is_ascii_whitespace())
+	}
+
+	/// `-}}` removes the whitespace after the statement.
+	fn is_right_trim(&self) -> bool {
+		self.current() == b'-' && self.is_match_at(self.current_index() + 1, self.syntax.end())
+	}
+
+	fn trim_previous_whitespace(&mut self) {
+		while let Some(token) = self.tokens.last_mut() {
+			match token.token() {
+				Token::NewLine => (),
+				Token::Raw(r) => match r.trim_end() {
+					"" => (),
+					trimmed => {
+						let trimmed = trimmed.to_string();
+						token.set_token(Token::Raw(trimmed));
+						return;
+					}
+				},
+				_ => return,
+			}
+			self.tokens.pop();
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while self.code_left() && self.current().is_ascii_whitespace() {
+			self.advance();
+		}
+	}
+
 	fn consume_start_delimiter(&mut self) {
-		if self.consume(START_DELIMITER) {
+		let start = self.current_index();
+		let delimiter = self.syntax.start().len();
+		if self.is_start_delimiter() {
+			self.index += delimiter;
 			self.in_stmt = true;
-			self.tokens.push(Token::StmtStart);
+			if self.is_left_trim() {
+				self.trim_previous_whitespace();
+				self.advance();
+				self.skip_whitespace();
+			}
+			self.push(Token::StmtStart, start);
 		}
 	}
 
 	fn consume_end_delimiter(&mut self) {
-		if self.obj_depth == 0 && self.consume(END_DELIMITER) {
+		if self.closing_depth() > 0 {
+			return;
+		}
+		let trim = self.is_right_trim();
+		if trim {
+			if self.tokens.last().is_some_and(|t| matches!(t.token(), Token::Whitespace)) {
+				self.tokens.pop();
+			}
+			self.advance();
+		}
+		let start = self.current_index();
+		let delimiter = self.syntax.end().len();
+		if self.is_end_delimiter() {
+			self.index += delimiter;
 			self.in_stmt = false;
-			self.tokens.push(Token::StmtEnd);
+			self.push(Token::StmtEnd, start);
+			if trim {
+				self.skip_whitespace();
+			}
 		}
 	}
 
@@ -205,7 +423,7 @@ impl<'a> OsmiaLexerScanner<'a> {
 
 	// }
 
-	fn consume_stmt_body(&mut self) -> Result<(), String> {
+	fn consume_stmt_body(&mut self) -> Result<(), OsmiaError> {
 		loop {
 			if !self.code_left() {
 				break;
@@ -223,23 +441,27 @@ impl<'a> OsmiaLexerScanner<'a> {
 	fn consume_comment(&mut self) {
 		fn add_piece(lexer: &mut OsmiaLexerScanner, start: usize, end: usize) {
 			if let Some(content) = lexer.pick_non_empty_string(start, end) {
-				lexer.tokens.push(Token::Raw(content));
+				lexer.push(Token::Raw(content), start);
 			}
 		}
-		self.tokens.push(Token::Comment);
+		self.push(Token::Comment, self.current_index() - self.syntax.comment().len());
 		let mut start = self.current_index();
 		let mut depth: usize = 0;
 		while self.code_left() {
 			if self.consume("\n") {
 				add_piece(self, start, self.current_index() - 1);
-				self.tokens.push(Token::NewLine);
+				self.push(Token::NewLine, self.current_index() - 1);
 				start = self.current_index();
 				continue;
 			}
-			if self.consume(START_DELIMITER) {
+			if self.is_start_delimiter() {
+				self.index += self.syntax.start().len();
 				depth += 1;
 			}
-			if self.is_match(END_DELIMITER) {
+			if depth == 0 && self.is_right_trim() {
+				break;
+			}
+			if self.is_end_delimiter() {
 				if depth == 0 {
 					break;
 				}
@@ -250,25 +472,43 @@ impl<'a> OsmiaLexerScanner<'a> {
 		add_piece(self, start, self.current_index());
 	}
 
-	fn consume_token(&mut self) -> Result<(), String> {
+	fn consume_token(&mut self) -> Result<(), OsmiaError> {
 		if !self.code_left() {
 			return Ok(());
 		}
 		if self.consume("{") {
-			self.tokens.push(Token::ObjectStart);
+			self.push(Token::ObjectStart, self.current_index() - 1);
 			self.obj_depth += 1;
 			return Ok(());
 		}
 		if self.obj_depth > 0 && self.consume("}") {
-			self.tokens.push(Token::ObjectEnd);
+			self.push(Token::ObjectEnd, self.current_index() - 1);
 			self.obj_depth -= 1;
 			return Ok(());
 		}
+		if self.consume("(") {
+			self.push(Token::ParentStart, self.current_index() - 1);
+			self.parent_depth += 1;
+			return Ok(());
+		}
+		if self.consume(")") {
+			self.push(Token::ParentEnd, self.current_index() - 1);
+			self.parent_depth = self.parent_depth.saturating_sub(1);
+			return Ok(());
+		}
+		if self.consume("[") {
+			self.push(Token::ArrayStart, self.current_index() - 1);
+			self.array_depth += 1;
+			return Ok(());
+		}
+		if self.consume("]") {
+			self.push(Token::ArrayEnd, self.current_index() - 1);
+			self.array_depth = self.array_depth.saturating_sub(1);
+			return Ok(());
+		}
 		if self.consume_in_order(vec![
-			("(", Token::ParentStart), (")", Token::ParentEnd),
-			("[", Token::ArrayStart), ("]", Token::ArrayEnd),
 			("+", Token::Plus), ("-", Token::Minus), ("*", Token::Mult), ("/", Token::Div), ("%", Token::Mod),
-			("...", Token::Spread), (".", Token::Dot),
+			("...", Token::Spread), ("..=", Token::RangeInclusive), ("..", Token::Range), (".", Token::Dot),
 			("=>", Token::Arrow),
 			("==", Token::Equal), ("=", Token::Assign),
 			("<=", Token::LessEqual), ("<<", Token::BitShiftLeft), ("<", Token::Less),
@@ -277,7 +517,8 @@ impl<'a> OsmiaLexerScanner<'a> {
 			("&&", Token::And), ("&", Token::BitAnd),
 			("||", Token::Or), ("|", Token::BitOr),
 			(",", Token::Comma), (":", Token::Colon),
-			(";", Token::Semicolon), ("?", Token::Question),
+			(";", Token::Semicolon),
+			("??", Token::NullCoalesce), ("?.", Token::SafeDot), ("?", Token::Question),
 			("^", Token::BitXor),
 		]) {
 			return Ok(());
@@ -294,7 +535,7 @@ impl<'a> OsmiaLexerScanner<'a> {
 		Ok(())
 	}
 
-	fn consume_int(&mut self) -> Result<String, String> {
+	fn consume_int(&mut self) -> Result<String, OsmiaError> {
 		let start = self.current_index();
 		while self.code_left() && self.current().is_ascii_digit() {
 			self.advance();
@@ -304,21 +545,27 @@ impl<'a> OsmiaLexerScanner<'a> {
 		))?)
 	}
 
-	fn consume_number(&mut self) -> Result<(), String> {
+	/// A dot after a number, unless it starts a range (`..`).
+	fn is_fraction_dot(&self) -> bool {
+		self.is_match(".") && !self.is_match("..")
+	}
+
+	fn consume_number(&mut self) -> Result<(), OsmiaError> {
+		let start = self.current_index();
 		let mut nbr = self.consume_int()?;
-		if self.code_left() && self.current() == b'.' {
+		if self.is_fraction_dot() {
 			self.advance();
 			let frac = self.consume_int()?;
-			if self.code_left() && self.current() == b'.' {
+			if self.is_fraction_dot() {
 				return Err(self.error("Unexpected dot in float number".to_string()));
 			}
 			nbr = format!("{}.{}", nbr, frac);
 		}
-		self.tokens.push(Token::Number(nbr));
+		self.push(Token::Number(nbr), start);
 		Ok(())
 	}
 
-	fn consume_string(&mut self) -> Result<(), String> {
+	fn consume_string(&mut self) -> Result<(), OsmiaError> {
 		let start = self.current_index();
 		let delim = self.current();
 		loop {
@@ -336,11 +583,11 @@ impl<'a> OsmiaLexerScanner<'a> {
 		}
 		self.advance();
 		let content = self.pick_string(start + 1, self.current_index() - 1);
-		self.tokens.push(Token::Str(content.unwrap()));
+		self.push(Token::Str(content.unwrap()), start);
 		Ok(())
 	}
 
-	fn consume_identifier(&mut self) -> Result<(), String> {
+	fn consume_identifier(&mut self) -> Result<(), OsmiaError> {
 		let start = self.current_index();
 		while self.code_left() && (self.current().is_ascii_alphanumeric() || self.current() == b'_') {
 			self.advance();
@@ -348,7 +595,7 @@ impl<'a> OsmiaLexerScanner<'a> {
 		let content: String = self.pick_string(start, self.current_index()).ok_or(self.error(
 			"Expected identifier".to_string()
 		))?;
-		self.tokens.push(match content.as_str() {
+		let token = match content.as_str() {
 			"print" => Token::Print,
 			"fn" => Token::Function,
 			"return" => Token::Return,
@@ -365,8 +612,36 @@ impl<'a> OsmiaLexerScanner<'a> {
 			"true" => Token::Bool(true),
 			"false" => Token::Bool(false),
 			"null" => Token::Null,
-			_ => Token::Alpha(content)
-		});
+			_ => self.statement_keyword(&content).unwrap_or(Token::Alpha(content))
+		};
+		self.push(token, start);
 		Ok(())
 	}
+
+	/// Keywords only reserved at the start of a statement, so they can still be
+	/// used as variable names: `parent` must be the whole statement and the rest
+	/// must be followed by their argument, like `{{file "a.txt"}}`.
+	fn statement_keyword(&self, content: &str) -> Option<Token> {
+		if !self.tokens.last().is_some_and(|t| matches!(t.token(), Token::StmtStart)) {
+			return None;
+		}
+		let mut next = self.current_index();
+		while next < self.code.len() && self.code[next].is_ascii_whitespace() {
+			next += 1;
+		}
+		let spaced = next > self.current_index();
+		let at_end = next >= self.code.len()
+			|| self.is_match_at(next, self.syntax.end())
+			|| (self.code[next] == b'-' && self.is_match_at(next + 1, self.syntax.end()));
+		match content {
+			"parent" if at_end => Some(Token::Parent),
+			_ if !spaced || at_end || self.code[next] == b'=' => None,
+			"include" => Some(Token::Include),
+			"extends" => Some(Token::Extends),
+			"block" => Some(Token::Block),
+			"capture" => Some(Token::Capture),
+			"file" => Some(Token::FileBlock),
+			_ => None,
+		}
+	}
 }
@@ -0,0 +1,74 @@
+use crate::types::OsmiaError;
+use crate::model::error::ErrorInfo;
+use crate::constants::{
+	START_DELIMITER,
+	END_DELIMITER,
+	COMMENT_MARKER,
+};
+
+/// Markers that delimit the Osmia statements inside the code.
+///
+/// By default, statements are written between `{{` and `}}`, and comments
+/// start with `{{#`. Changing them allows rendering files whose content
+/// would otherwise conflict with the default markers.
+///
+/// ```rust
+/// use osmia::{Osmia, Syntax};
+///
+/// let mut osmia = Osmia::try_from(r#"{ "name": "Marvin" }"#).unwrap()
+///   .with_syntax(Syntax::new("<%", "%>").with_comment("--"));
+/// assert_eq!(
+///   osmia.run_code("{{ .Values.name }}: <% name %><%-- ignored %>").unwrap(),
+///   "{{ .Values.name }}: Marvin"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syntax {
+	start: String,
+	end: String,
+	comment: String,
+}
+
+impl Syntax {
+	pub fn new(start: &str, end: &str) -> Self {
+		Self {
+			start: start.into(),
+			end: end.into(),
+			comment: COMMENT_MARKER.into(),
+		}
+	}
+
+	/// Sets the marker that, right after the start delimiter, begins a comment.
+	pub fn with_comment(mut self, comment: &str) -> Self {
+		self.comment = comment.into();
+		self
+	}
+
+	pub fn start(&self) -> &str {
+		&self.start
+	}
+
+	pub fn end(&self) -> &str {
+		&self.end
+	}
+
+	pub fn comment(&self) -> &str {
+		&self.comment
+	}
+
+	pub(crate) fn validate(&self) -> Result<(), OsmiaError> {
+		if self.start.is_empty() || self.end.is_empty() || self.comment.is_empty() {
+			return Err(OsmiaError::Lexer(ErrorInfo::new(format!(
+				"Invalid syntax {:?} {:?} {:?}: the markers cannot be empty",
+				self.start, self.end, self.comment
+			))));
+		}
+		Ok(())
+	}
+}
+
+impl Default for Syntax {
+	fn default() -> Self {
+		Self::new(START_DELIMITER, END_DELIMITER)
+	}
+}
@@ -24,6 +24,14 @@ pub enum Token {
 	Comment,
 	Function,
 	Return,
+	Include,
+	Extends,
+	Block,
+	Parent,
+	RawBlock,
+	EndRawBlock,
+	Capture,
+	FileBlock,
 
 	// Conditionals
 	If,
@@ -84,7 +92,11 @@ pub enum Token {
 	Colon,
 	Semicolon,
 	Spread,
+	Range,
+	RangeInclusive,
 	Question,
+	NullCoalesce,
+	SafeDot,
 	Arrow,
 }
 
@@ -176,6 +188,14 @@ impl_token_traits!(
 	Comment <=> "#",
 	Function <=> "fn",
 	Return <=> "return",
+	Include <=> "include",
+	Extends <=> "extends",
+	Block <=> "block",
+	Parent <=> "parent",
+	RawBlock <=> "raw",
+	EndRawBlock <=> "endraw",
+	Capture <=> "capture",
+	FileBlock <=> "file",
 
 	// Conditionals
 	If <=> "if",
@@ -232,6 +252,10 @@ impl_token_traits!(
 	Colon <=> ":",
 	Semicolon <=> ";",
 	Spread <=> "...",
+	Range <=> "..",
+	RangeInclusive <=> "..=",
 	Question <=> "?",
+	NullCoalesce <=> "??",
+	SafeDot <=> "?.",
 	Arrow <=> "=>"
 );
@@ -0,0 +1,35 @@
+use std::path::{
+	Component,
+	Path,
+	PathBuf,
+};
+
+use super::TemplateLoader;
+use crate::types::OsmiaError;
+
+/// Loader that reads the templates from the files inside a directory.
+///
+/// The names are paths relative to the root directory. Names that would
+/// leave it, like absolute paths or paths with `..`, are rejected.
+pub struct FsLoader {
+	root: PathBuf,
+}
+
+impl FsLoader {
+	pub fn new(root: impl Into<PathBuf>) -> Self {
+		Self { root: root.into() }
+	}
+}
+
+impl TemplateLoader for FsLoader {
+	fn load(&self, name: &str) -> Result<String, OsmiaError> {
+		let path = Path::new(name);
+		if !path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+			return Err(format!("Invalid template name: {}", name).into());
+		}
+		match std::fs::read_to_string(self.root.join(path)) {
+			Ok(code) => Ok(code),
+			Err(e) => Err(format!("Template not found: {}: {}", name, e).into()),
+		}
+	}
+}
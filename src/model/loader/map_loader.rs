@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use super::TemplateLoader;
+use crate::types::OsmiaError;
+
+/// Loader that keeps the templates in memory.
+pub struct MapLoader {
+	templates: HashMap<String, String>,
+}
+
+impl MapLoader {
+	pub fn new() -> Self {
+		Self { templates: HashMap::new() }
+	}
+
+	pub fn add(mut self, name: &str, code: &str) -> Self {
+		self.templates.insert(name.to_string(), code.to_string());
+		self
+	}
+}
+
+impl Default for MapLoader {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl From<HashMap<String, String>> for MapLoader {
+	fn from(templates: HashMap<String, String>) -> Self {
+		Self { templates }
+	}
+}
+
+impl TemplateLoader for MapLoader {
+	fn load(&self, name: &str) -> Result<String, OsmiaError> {
+		match self.templates.get(name) {
+			Some(code) => Ok(code.clone()),
+			None => Err(format!("Template not found: {}", name).into()),
+		}
+	}
+}
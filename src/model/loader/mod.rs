@@ -0,0 +1,11 @@
+mod fs_loader;
+mod map_loader;
+mod template_cache;
+mod template_loader;
+mod template_resolver;
+
+pub use fs_loader::FsLoader;
+pub use map_loader::MapLoader;
+pub use template_cache::TemplateCache;
+pub use template_loader::TemplateLoader;
+pub use template_resolver::TemplateResolver;
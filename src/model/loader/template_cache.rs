@@ -0,0 +1,37 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::types::{
+	ParserCode,
+	OsmiaError,
+};
+
+/// Templates already loaded and parsed, along with their code.
+///
+/// Every [Template](crate::Template) keeps one, so its includes are only
+/// loaded and parsed the first time they are rendered.
+#[derive(Default)]
+pub struct TemplateCache {
+	templates: RefCell<HashMap<String, Rc<(String, ParserCode)>>>,
+}
+
+impl TemplateCache {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the template with the given name, using `load` if it is not cached yet.
+	pub fn get_or_load(
+		&self,
+		name: &str,
+		load: impl FnOnce() -> Result<(String, ParserCode), OsmiaError>
+	) -> Result<Rc<(String, ParserCode)>, OsmiaError> {
+		if let Some(template) = self.templates.borrow().get(name) {
+			return Ok(template.clone());
+		}
+		let template = Rc::new(load()?);
+		self.templates.borrow_mut().insert(name.to_string(), template.clone());
+		Ok(template)
+	}
+}
@@ -0,0 +1,21 @@
+use crate::types::OsmiaError;
+
+/// Resolves the name of a template into its code.
+///
+/// It is used by the statements that reference other templates, like `include`.
+///
+/// ```rust
+/// use osmia::{Osmia, MapLoader};
+///
+/// let loader = MapLoader::new()
+///   .add("header", "<h1>{{ title }}</h1>");
+/// let mut osmia = Osmia::try_from(r#"{ "title": "Osmia" }"#).unwrap()
+///   .with_loader(loader);
+/// assert_eq!(
+///   osmia.run_code(r#"{{include "header"}}"#).unwrap(),
+///   "<h1>Osmia</h1>"
+/// );
+/// ```
+pub trait TemplateLoader {
+	fn load(&self, name: &str) -> Result<String, OsmiaError>;
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use super::TemplateLoader;
+use crate::types::{
+	ParserCode,
+	OsmiaError,
+};
+use crate::model::stmt::Stmt;
+use crate::model::lexer::{
+	Lexer,
+	OsmiaLexer,
+	Syntax,
+};
+use crate::model::parser::{
+	Parser,
+	OsmiaParser,
+};
+
+/// Resolves the templates referenced by the code using a [TemplateLoader].
+///
+/// A template that extends another one is replaced by the template it
+/// extends, where the named blocks are overridden by the ones of the child.
+pub struct TemplateResolver<'a> {
+	loader: Option<&'a dyn TemplateLoader>,
+	syntax: Syntax,
+}
+
+impl<'a> TemplateResolver<'a> {
+	pub fn new(loader: Option<&'a dyn TemplateLoader>) -> Self {
+		Self { loader, syntax: Syntax::default() }
+	}
+
+	/// Sets the [Syntax] used to lex the loaded templates.
+	pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+		self.syntax = syntax;
+		self
+	}
+
+	/// Loads, parses and resolves the template with the given name.
+	///
+	/// The code of the template is returned along with the result.
+	pub fn load(&self, name: &str) -> Result<(String, ParserCode), OsmiaError> {
+		self.load_extending(name, &mut Vec::new())
+	}
+
+	pub fn resolve(&self, code: ParserCode) -> Result<ParserCode, OsmiaError> {
+		self.resolve_extending(code, &mut Vec::new())
+	}
+
+	fn load_extending(
+		&self,
+		name: &str,
+		chain: &mut Vec<String>
+	) -> Result<(String, ParserCode), OsmiaError> {
+		let loader = match self.loader {
+			Some(loader) => loader,
+			None => return Err(format!("Cannot load {:?} without a template loader", name).into()),
+		};
+		let code = loader.load(name)?;
+		let parsed = OsmiaLexer::new().with_syntax(self.syntax.clone()).lex(&code)
+			.and_then(|tokens| OsmiaParser::new().parse(tokens))
+			.map_err(|e| e.with_code_trace(&code))?;
+		let resolved = self.resolve_extending(parsed, chain)?;
+		Ok((code, resolved))
+	}
+
+	fn resolve_extending(&self, mut code: ParserCode, chain: &mut Vec<String>) -> Result<ParserCode, OsmiaError> {
+		let name = match Self::extended_template(&code)? {
+			Some(name) => name,
+			None => return Ok(code),
+		};
+		if chain.contains(&name) {
+			let mut cycle = chain.clone();
+			cycle.push(name);
+			return Err(format!("Extends cycle detected: {}", cycle.join(" -> ")).into());
+		}
+		chain.push(name.clone());
+		let (_, mut parent) = self.load_extending(&name, chain)?;
+		chain.pop();
+		let mut blocks = HashMap::new();
+		Self::collect_blocks(&mut code, &mut blocks)?;
+		Self::override_blocks(&mut parent, &blocks);
+		Ok(parent)
+	}
+
+	fn extended_template(code: &ParserCode) -> Result<Option<String>, OsmiaError> {
+		let stmts = match code {
+			Stmt::Block(block) => block.stmts().iter().collect(),
+			stmt => vec![stmt],
+		};
+		let mut names = stmts.into_iter().filter_map(|stmt| match stmt {
+			Stmt::Extends(name) => Some(name.clone()),
+			_ => None,
+		});
+		match (names.next(), names.next()) {
+			(name, None) => Ok(name),
+			(Some(_), Some(_)) | (None, Some(_)) => Err("A template can only extend one template".into()),
+		}
+	}
+
+	fn collect_blocks(stmt: &mut Stmt, blocks: &mut HashMap<String, Stmt>) -> Result<(), OsmiaError> {
+		if let Stmt::NamedBlock(block) = stmt {
+			if blocks.insert(block.name().to_string(), block.body().clone()).is_some() {
+				return Err(format!("Block {:?} is defined more than once", block.name()).into());
+			}
+		}
+		for child in stmt.children_mut() {
+			Self::collect_blocks(child, blocks)?;
+		}
+		Ok(())
+	}
+
+	fn override_blocks(stmt: &mut Stmt, blocks: &HashMap<String, Stmt>) {
+		if let Stmt::NamedBlock(block) = stmt {
+			if let Some(body) = blocks.get(block.name()) {
+				let mut body = body.clone();
+				Self::replace_parent(&mut body, block.body());
+				*block.body_mut() = body;
+			}
+		}
+		for child in stmt.children_mut() {
+			Self::override_blocks(child, blocks);
+		}
+	}
+
+	fn replace_parent(stmt: &mut Stmt, parent: &Stmt) {
+		match stmt {
+			Stmt::Parent => *stmt = parent.clone(),
+			Stmt::NamedBlock(_) => (),
+			_ => for child in stmt.children_mut() {
+				Self::replace_parent(child, parent);
+			},
+		}
+	}
+}
@@ -1,6 +1,8 @@
 pub mod ctx;
+pub mod error;
 pub mod interpreter;
 pub mod lexer;
+pub mod loader;
 pub mod parser;
 pub mod stmt;
 pub mod expr;
@@ -10,6 +10,10 @@ use crate::types::{
 use crate::model::expr::*;
 use crate::model::stmt::*;
 use crate::model::ctx::JsonTreeKey;
+use crate::model::error::{
+	ErrorInfo,
+	Location,
+};
 
 pub struct OsmiaParser;
 
@@ -25,30 +29,49 @@ impl Parser<LexerCode, ParserCode, OsmiaError> for OsmiaParser {
 	}
 }
 
+impl OsmiaParser {
+	/// Parses the code recovering from the syntax errors, so all of them are reported.
+	pub fn parse_all(&self, code: LexerCode) -> Result<ParserCode, Vec<OsmiaError>> {
+		OsmiaParserImpl::new(code).parse_all()
+	}
+}
+
 use crate::model::lexer::Token;
 
 pub struct OsmiaParserImpl {
-	line: usize,
 	code: LexerCode,
 	current: usize,
+	errors: Vec<OsmiaError>,
 }
 
 impl OsmiaParserImpl {
 	pub fn new(code: LexerCode) -> Self {
 		Self {
-			line: 1, code,
-			current: 0
+			code,
+			current: 0,
+			errors: Vec::new(),
 		}
 	}
 
 	pub fn parse(&mut self) -> Result<ParserCode, OsmiaError> {
-		let code = self.code()?;
+		self.parse_all().map_err(|mut errors| errors.remove(0))
+	}
+
+	/// Parses the whole code, collecting every error found instead of stopping at the first one.
+	pub fn parse_all(&mut self) -> Result<ParserCode, Vec<OsmiaError>> {
+		let code = self.code();
 		if !self.done() {
-			return Err(self.error_msg(
+			let error = self.error_msg(
 				ParserErrorMsg::Custom("Unexpected token".to_string())
-			));
+			);
+			self.errors.push(error);
+		}
+		match code {
+			Err(error) => self.errors.push(error),
+			Ok(code) if self.errors.is_empty() => return Ok(code),
+			Ok(_) => (),
 		}
-		Ok(code)
+		Err(std::mem::take(&mut self.errors))
 	}
 
 	fn code(&mut self) -> Result<ParserCode, OsmiaError> {
@@ -57,20 +80,11 @@ impl OsmiaParserImpl {
 }
 
 impl OsmiaParserImpl {
-	#[cfg(debug_assertions)]
-	fn error_msg(&self, msg: ParserErrorMsg) -> String {
-		let tokens_until_now = &self.code[0..self.current];
-		format!(
-			"Parser error: Line {}: {}\nTokens until now: {:?} -> {:?}",
-			self.line, msg.report(self), tokens_until_now, self.get_current()
-		)
-	}
-
-	#[cfg(not(debug_assertions))]
-	fn error_msg(&self, msg: ParserErrorMsg) -> String {
-		format!(
-			"Parser error: Line {}: {}",
-			self.line, msg.report(self),
+	fn error_msg(&self, msg: ParserErrorMsg) -> OsmiaError {
+		OsmiaError::Parser(
+			ErrorInfo::new(msg.report(self))
+				.with_location(self.current_location())
+				.with_culprit(format!("{:?}", self.get_current()))
 		)
 	}
 
@@ -83,11 +97,19 @@ impl OsmiaParserImpl {
 	}
 
 	pub fn get_current(&self) -> &Token {
-		&self.code[self.current]
+		self.code[self.current].token()
 	}
 
 	fn get_previous(&self) -> &Token {
-		&self.code[self.current - 1]
+		self.code[self.current - 1].token()
+	}
+
+	fn current_location(&self) -> Location {
+		self.code[self.current].location()
+	}
+
+	fn previous_location(&self) -> Location {
+		self.code[self.current - 1].location()
 	}
 
 	fn advance(&mut self) -> &Token {
@@ -129,12 +151,13 @@ impl OsmiaParserImpl {
 		self.consume_whitespaces();
 		while self.match_and_advance(ops) {
 			let op: Option<BinaryOp> = self.get_previous().into();
+			let location = self.previous_location();
 			self.consume_whitespaces();
 			expr = Binary::new(
 				expr,
 				op.unwrap(),
 				side(self)?,
-			).into();
+			).with_location(location).into();
 			self.consume_whitespaces();
 		}
 		Ok(expr)
@@ -145,8 +168,7 @@ impl OsmiaParserImpl {
 	fn consume_new_lines(&mut self) {
 		while !self.done() {
 			match self.get_current() {
-				Token::NewLine => self.line += 1,
-				Token::NewLineNonPrintable => self.line += 1,
+				Token::NewLine | Token::NewLineNonPrintable => (),
 				_ => return
 			}
 			self.advance();
@@ -176,13 +198,21 @@ impl OsmiaParserImpl {
 				Token::NewLineNonPrintable => statements.push(Stmt::NewLineNonPrintable),
 				Token::Raw(r) => statements.push(Stmt::Raw(r.to_string())),
 				Token::NonPrintable(r) => statements.push(Stmt::NonPrintable(r.to_string())),
-				Token::StmtStart => match self.stmt(break_with)? {
-					None => break,
-					Some(stmt) => statements.push(stmt),
+				Token::StmtStart => {
+					let start = self.current;
+					match self.stmt(break_with) {
+						Ok(None) => break,
+						Ok(Some(stmt)) => statements.push(stmt),
+						Err(error) => self.recover(error, start),
+					}
 				},
-				_ => return Err(self.error_msg(
-					ParserErrorMsg::Custom("Unexpected in block:".to_string())
-				))
+				_ => {
+					let error = self.error_msg(
+						ParserErrorMsg::Custom("Unexpected in block:".to_string())
+					);
+					self.errors.push(error);
+					self.synchronize();
+				}
 			}
 		}
 		if statements.len() == 1 {
@@ -192,6 +222,46 @@ impl OsmiaParserImpl {
 		Ok(statements.into())
 	}
 
+	/// Stores the error and skips the rest of the failed statement.
+	///
+	/// If the error was in the opening statement of a block, its body is
+	/// parsed too, so the errors inside it are reported and its closing
+	/// keyword does not end the parent block.
+	fn recover(&mut self, error: OsmiaError, start: usize) {
+		self.errors.push(error);
+		let failed_stmt_start = self.code[start..self.current].iter()
+			.rposition(|token| token.token() == &Token::StmtStart)
+			.map(|i| start + i + 1)
+			.unwrap_or(start);
+		let keyword = self.code[failed_stmt_start].token().clone();
+		self.synchronize();
+		let break_with = match keyword {
+			Token::If | Token::ElseIf | Token::Else => vec![
+				Token::ElseIf, Token::Else, Token::Fi
+			],
+			Token::While | Token::For | Token::Function |
+			Token::Block | Token::Capture | Token::FileBlock => vec![Token::Done],
+			_ => return,
+		};
+		while !self.done() {
+			let _ = self.breakable_block(Some(&break_with));
+			let is_middle_stmt = self.check_current(&Token::ElseIf) || self.check_current(&Token::Else);
+			self.synchronize();
+			if !is_middle_stmt {
+				return;
+			}
+		}
+	}
+
+	/// Skips tokens until the end of the current statement or the start of the next one.
+	fn synchronize(&mut self) {
+		while !self.done() && !self.check_current(&Token::StmtStart) {
+			if self.advance() == &Token::StmtEnd {
+				return;
+			}
+		}
+	}
+
 	fn stmt(&mut self, return_none_with: Option<&Vec<Token>>) -> Result<Option<Stmt>, OsmiaError> {
 		if let Some(break_blocks) = return_none_with {
 			let is_end_token = self.check_current(&Token::Fi) || self.check_current(&Token::Done);
@@ -214,6 +284,13 @@ impl OsmiaParserImpl {
 			Token::Continue => self.continue_stmt()?,
 			Token::Return => self.return_stmt()?,
 			Token::Function => self.function()?,
+			Token::Include => self.include()?,
+			Token::Extends => self.extends()?,
+			Token::Block => self.named_block()?,
+			Token::Parent => self.parent()?,
+			Token::RawBlock => self.raw_block()?,
+			Token::Capture => self.capture()?,
+			Token::FileBlock => self.file_block()?,
 			_ => self.assign()?,
 		};
 		self.consume(Token::StmtEnd, |parser| parser.error_msg(
@@ -238,10 +315,7 @@ impl OsmiaParserImpl {
 		while !self.done() && !self.check_current(&Token::StmtEnd) {
 			match self.advance() {
 				Token::Raw(r) | Token::NonPrintable(r) => comment.push_str(r),
-				Token::NewLine | Token::NewLineNonPrintable => {
-					comment.push('\n');
-					self.line += 1;
-				},
+				Token::NewLine | Token::NewLineNonPrintable => comment.push('\n'),
 				_ => return Err(self.error_msg(
 					ParserErrorMsg::ParseValue("comment".into())
 				))
@@ -250,15 +324,61 @@ impl OsmiaParserImpl {
 		Ok(Stmt::Comment(comment))
 	}
 
+	fn raw_block(&mut self) -> Result<Stmt, OsmiaError> {
+		self.consume(Token::RawBlock, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::RawBlock)
+		))?;
+		self.consume(Token::StmtEnd, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("raw block".to_string(), Token::StmtEnd)
+		))?;
+		let mut statements: Block = Block::new();
+		if self.match_and_advance(&[Token::NewLineNonPrintable]) {
+			statements.push(Stmt::NewLineNonPrintable);
+		}
+		let mut line_start = true;
+		while !self.done() && !self.check_current(&Token::StmtStart) {
+			// The body is printed as written, only the line of the closing statement is cleaned
+			let token = self.advance().clone();
+			let closing_line = line_start && self.check_current(&Token::StmtStart);
+			let stmt = match token {
+				Token::NonPrintable(r) if closing_line => Stmt::NonPrintable(r),
+				Token::Raw(r) | Token::NonPrintable(r) => Stmt::Raw(r),
+				Token::NewLine | Token::NewLineNonPrintable => Stmt::NewLine,
+				_ => return Err(self.error_msg(
+					ParserErrorMsg::ParseValue("raw block".into())
+				))
+			};
+			line_start = matches!(stmt, Stmt::NewLine);
+			statements.push(stmt);
+		}
+		self.consume(Token::StmtStart, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("raw block".to_string(), Token::EndRawBlock)
+		))?;
+		self.consume(Token::EndRawBlock, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("raw block".to_string(), Token::EndRawBlock)
+		))?;
+		if statements.len() == 1 {
+			let mut arr: Vec<Stmt> = statements.into();
+			return Ok(arr.pop().unwrap());
+		}
+		Ok(statements.into())
+	}
+
 	fn assign(&mut self) -> Result<Stmt, OsmiaError> {
 		self.consume_whitespaces();
+		let location = self.current_location();
 		let expr = self.expr()?;
 		self.consume_whitespaces();
 		if let Expr::Variable(var) = expr {
+			if var.is_safe() && self.check_current(&Token::Assign) {
+				return Err(self.error_msg(
+					ParserErrorMsg::Custom("Safe navigation cannot be used to assign a value. Found".to_string())
+				));
+			}
 			return match self.match_and_advance(&[Token::Assign]) {
 				true => {
 					self.consume_whitespaces();
-					Ok(Stmt::new_assign(var, self.expr()?))
+					Ok(Stmt::Assign(Assign::new(var, self.expr()?).with_location(location)))
 				},
 				false => Ok(Expr::Variable(var).into())
 			}
@@ -303,12 +423,20 @@ impl OsmiaParserImpl {
 	}
 
 	fn for_stmt(&mut self) -> Result<Stmt, OsmiaError> {
+		let location = self.current_location();
 		self.consume(Token::For, |parser| parser.error_msg(
 			ParserErrorMsg::MissingKeyword(Token::For)
 		))?;
 		self.consume_whitespaces();
-		let var = Variable::from_vec(vec![self.identifier()?.into()]);
+		let mut key = None;
+		let mut pattern = self.pattern()?;
 		self.consume_whitespaces();
+		if self.match_and_advance(&[Token::Comma]) {
+			self.consume_whitespaces();
+			key = Some(pattern);
+			pattern = self.pattern()?;
+			self.consume_whitespaces();
+		}
 		self.consume(Token::In, |parser| parser.error_msg(
 			ParserErrorMsg::MissingKeyword(Token::In)
 		))?;
@@ -319,7 +447,67 @@ impl OsmiaParserImpl {
 			ParserErrorMsg::Unclosed("for statement".to_string(), Token::StmtEnd)
 		))?;
 		let block = self.breakable_block(Some(&vec![Token::Done]))?;
-		Ok(Stmt::For(For::new(var, iterable, block)))
+		let mut for_stmt = For::new(pattern, iterable, block).with_location(location);
+		if let Some(key) = key {
+			for_stmt = for_stmt.with_key(key);
+		}
+		Ok(Stmt::For(for_stmt))
+	}
+
+	fn pattern(&mut self) -> Result<Pattern, OsmiaError> {
+		match self.get_current() {
+			Token::ObjectStart => self.object_pattern(),
+			Token::ArrayStart => self.array_pattern(),
+			_ => Ok(Variable::from_name(self.identifier()?.into()).into()),
+		}
+	}
+
+	fn object_pattern(&mut self) -> Result<Pattern, OsmiaError> {
+		self.consume(Token::ObjectStart, |parser| parser.error_msg(
+			ParserErrorMsg::Expected(Token::ObjectStart)
+		))?;
+		let mut entries = Vec::new();
+		loop {
+			self.consume_whitespaces();
+			let key = match self.identifier()? {
+				JsonTreeKey::Key(key) => key,
+				JsonTreeKey::Index(_) => unreachable!(),
+			};
+			self.consume_whitespaces();
+			let pattern = match self.match_and_advance(&[Token::Colon]) {
+				true => {
+					self.consume_whitespaces();
+					self.pattern()?
+				},
+				false => Variable::from_name(JsonTreeKey::Key(key.clone()).into()).into(),
+			};
+			entries.push((key, pattern));
+			self.consume_whitespaces();
+			if self.match_and_advance(&[Token::ObjectEnd]) {
+				return Ok(Pattern::Object(entries));
+			}
+			self.consume(Token::Comma, |parser| parser.error_msg(
+				ParserErrorMsg::Unclosed("object pattern".to_string(), Token::ObjectEnd)
+			))?;
+		}
+	}
+
+	fn array_pattern(&mut self) -> Result<Pattern, OsmiaError> {
+		self.consume(Token::ArrayStart, |parser| parser.error_msg(
+			ParserErrorMsg::Expected(Token::ArrayStart)
+		))?;
+		let mut patterns = Vec::new();
+		loop {
+			self.consume_whitespaces();
+			patterns.push(self.pattern()?);
+			self.consume_whitespaces();
+			if self.match_and_advance(&[Token::ArrayEnd]) {
+				return Ok(Pattern::Array(patterns));
+			}
+			self.consume(Token::Comma, |parser| parser.error_msg(
+				ParserErrorMsg::Unclosed("array pattern".to_string(), Token::ArrayEnd)
+			))?;
+		}
 	}
 
 	fn conditional(&mut self, break_with: &Vec<Token>) -> Result<ConditionalStmt, OsmiaError> {
@@ -378,6 +566,89 @@ impl OsmiaParserImpl {
 		Ok(Stmt::Function(Function::new(name, params, block)))
 	}
 
+	fn include(&mut self) -> Result<Stmt, OsmiaError> {
+		let location = self.current_location();
+		self.consume(Token::Include, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::Include)
+		))?;
+		self.consume_whitespaces();
+		let name = self.expr()?;
+		self.consume_whitespaces();
+		Ok(Stmt::Include(Include::new(name).with_location(location)))
+	}
+
+	fn extends(&mut self) -> Result<Stmt, OsmiaError> {
+		self.consume(Token::Extends, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::Extends)
+		))?;
+		self.consume_whitespaces();
+		let name = match self.get_current() {
+			Token::Str(s) => s.to_string(),
+			_ => return Err(self.error_msg(
+				ParserErrorMsg::Custom("Expected the name of the template as a string but got".to_string())
+			)),
+		};
+		self.advance();
+		self.consume_whitespaces();
+		Ok(Stmt::Extends(name))
+	}
+
+	fn named_block(&mut self) -> Result<Stmt, OsmiaError> {
+		self.consume(Token::Block, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::Block)
+		))?;
+		self.consume_whitespaces();
+		let name = self.identifier()?.to_string();
+		self.consume_whitespaces();
+		self.consume(Token::StmtEnd, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("block statement".to_string(), Token::StmtEnd)
+		))?;
+		let body = self.breakable_block(Some(&vec![Token::Done]))?;
+		Ok(Stmt::NamedBlock(NamedBlock::new(&name, body)))
+	}
+
+	fn capture(&mut self) -> Result<Stmt, OsmiaError> {
+		let location = self.current_location();
+		self.consume(Token::Capture, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::Capture)
+		))?;
+		self.consume_whitespaces();
+		let var = match self.expr()? {
+			Expr::Variable(var) if !var.is_safe() => var,
+			_ => return Err(self.error_msg(
+				ParserErrorMsg::Custom("The captured output can only be stored in a variable. Found an expression before".to_string())
+			)),
+		};
+		self.consume_whitespaces();
+		self.consume(Token::StmtEnd, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("capture statement".to_string(), Token::StmtEnd)
+		))?;
+		let body = self.breakable_block(Some(&vec![Token::Done]))?;
+		Ok(Stmt::Capture(Capture::new(var, body).with_location(location)))
+	}
+
+	fn file_block(&mut self) -> Result<Stmt, OsmiaError> {
+		let location = self.current_location();
+		self.consume(Token::FileBlock, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::FileBlock)
+		))?;
+		self.consume_whitespaces();
+		let path = self.expr()?;
+		self.consume_whitespaces();
+		self.consume(Token::StmtEnd, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("file statement".to_string(), Token::StmtEnd)
+		))?;
+		let body = self.breakable_block(Some(&vec![Token::Done]))?;
+		Ok(Stmt::FileBlock(FileBlock::new(path, body).with_location(location)))
+	}
+
+	fn parent(&mut self) -> Result<Stmt, OsmiaError> {
+		self.consume(Token::Parent, |parser| parser.error_msg(
+			ParserErrorMsg::MissingKeyword(Token::Parent)
+		))?;
+		Ok(Stmt::Parent)
+	}
+
 	fn parameters(&mut self, exit_token: &Token) -> Result<Vec<FunctionParam>, OsmiaError> {
 		self.consume_whitespaces();
 		let mut are_mandatory_params_allowed = true;
@@ -397,10 +668,10 @@ impl OsmiaParserImpl {
 			let param = self.parameter()?;
 			match &param {
 				FunctionParam::Param(_, None) if !are_mandatory_params_allowed => {
-					return Err(format!(
-						"Invalid parameter: A mandatory parameter can not be after an optional parameter: {}",
+					return Err(self.error_msg(ParserErrorMsg::InvalidParameter(format!(
+						"A mandatory parameter can not be after an optional parameter: {}",
 						param
-					));
+					))));
 				},
 				FunctionParam::Param(_, Some(_)) if are_mandatory_params_allowed => {
 					are_mandatory_params_allowed = false;
@@ -427,10 +698,28 @@ impl OsmiaParserImpl {
 	fn expr(&mut self) -> Result<Expr, OsmiaError> {
 		match self.get_current() {
 			Token::Function => self.lambda(),
-			_ => self.logic_or()
+			_ => self.ternary()
 		}
 	}
 
+	fn ternary(&mut self) -> Result<Expr, OsmiaError> {
+		let then = self.null_coalescing()?;
+		self.consume_whitespaces();
+		if !self.match_and_advance(&[Token::If]) {
+			return Ok(then);
+		}
+		let location = self.previous_location();
+		self.consume_whitespaces();
+		let condition = self.null_coalescing()?;
+		self.consume_whitespaces();
+		self.consume(Token::Else, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("inline conditional".to_string(), Token::Else)
+		))?;
+		self.consume_whitespaces();
+		let otherwise = self.expr()?;
+		Ok(Ternary::new(condition, then, otherwise).with_location(location).into())
+	}
+
 	fn lambda(&mut self) -> Result<Expr, OsmiaError> {
 		self.consume(Token::Function, |parser| parser.error_msg(
 			ParserErrorMsg::MissingKeyword(Token::Function)
@@ -452,6 +741,10 @@ impl OsmiaParserImpl {
 		Ok(Lambda::new(params, body).into())
 	}
 
+	fn null_coalescing(&mut self) -> Result<Expr, OsmiaError> {
+		self.binary(&[Token::NullCoalesce], |parser| parser.logic_or())
+	}
+
 	fn logic_or(&mut self) -> Result<Expr, OsmiaError> {
 		self.binary(&[Token::Or], |parser| parser.logic_and())
 	}
@@ -471,10 +764,14 @@ impl OsmiaParserImpl {
 	fn comparison(&mut self) -> Result<Expr, OsmiaError> {
 		self.binary(
 			&[Token::Greater, Token::GreaterEqual, Token::Less, Token::LessEqual],
-			|parser| parser.bitshift(),
+			|parser| parser.range(),
 		)
 	}
 
+	fn range(&mut self) -> Result<Expr, OsmiaError> {
+		self.binary(&[Token::Range, Token::RangeInclusive], |parser| parser.bitshift())
+	}
+
 	fn bitshift(&mut self) -> Result<Expr, OsmiaError> {
 		self.binary(&[Token::BitShiftLeft, Token::BitShiftRight], |parser| parser.term())
 	}
@@ -490,19 +787,21 @@ impl OsmiaParserImpl {
 	fn unary(&mut self) -> Result<Expr, OsmiaError> {
 		if self.match_and_advance(&[Token::Not, Token::Minus, Token::Plus]) {
 			let operator: Option<UnaryOp> = self.get_previous().into();
+			let location = self.previous_location();
 			let right = self.unary()?;
-			return Ok(Unary::new(operator.unwrap(), right).into());
+			return Ok(Unary::new(operator.unwrap(), right).with_location(location).into());
 		}
 		self.value()
 	}
 
 	fn value(&mut self) -> Result<Expr, OsmiaError> {
+		let location = self.current_location();
 		let mut value = self.primary()?;
 		loop {
 			value = match self.get_current() {
 				Token::Question => self.method_call(value)?,
-				Token::ParentStart => self.call(value)?,
-				Token::Dot | Token::ArrayStart => self.variable(value)?,
+				Token::ParentStart => self.call(value, location)?,
+				Token::Dot | Token::SafeDot | Token::ArrayStart => self.variable(value, location)?,
 				_ => break,
 			}
 		}
@@ -515,7 +814,10 @@ impl OsmiaParserImpl {
 			Token::ObjectStart => self.object(),
 			Token::ParentStart => self.grouping(),
 			Token::Str(_) | Token::Number(_) | Token::Bool(_) | Token::Null => self.literal(),
-			_ => Ok(Variable::from_name(self.identifier()?.into()).into()),
+			_ => {
+				let location = self.current_location();
+				Ok(Variable::from_name(self.identifier()?.into()).with_location(location).into())
+			},
 		}
 	}
 
@@ -614,9 +916,10 @@ impl OsmiaParserImpl {
 
 	fn method_call(&mut self, mut obj: Expr) -> Result<Expr, OsmiaError> {
 		while self.match_and_advance(&[Token::Question]) {
+			let location = self.previous_location();
 			let name = Variable::from_name(self.identifier()?.into()).into();
-			match self.call(name)? {
-				Expr::Call(call) => obj = MethodCall::new(obj, call).into(),
+			match self.call(name, location)? {
+				Expr::Call(call) => obj = MethodCall::new(obj, call).with_location(location).into(),
 				_ => return Err(self.error_msg(
 					ParserErrorMsg::Custom(
 						"Expected method call. Maybe you forgot to call it?".to_string()
@@ -627,9 +930,9 @@ impl OsmiaParserImpl {
 		Ok(obj)
 	}
 
-	fn call(&mut self, mut callable: Expr) -> Result<Expr, OsmiaError> {
+	fn call(&mut self, mut callable: Expr, location: Location) -> Result<Expr, OsmiaError> {
 		while self.check_current(&Token::ParentStart) {
-			callable = Call::new(callable, self.arguments()?).into();
+			callable = Call::new(callable, self.arguments()?).with_location(location).into();
 		}
 		Ok(callable)
 	}
@@ -658,38 +961,51 @@ impl OsmiaParserImpl {
 		Ok(arr)
 	}
 
-	fn variable(&mut self, name: Expr) -> Result<Expr, OsmiaError> {
-		let mut var: Vec<JsonTreeKeyExpr> = vec![];
-		match name {
-			Expr::Variable(v) => var = v.into(),
-			e => var.push(e.into())
-		}
+	fn variable(&mut self, name: Expr, location: Location) -> Result<Expr, OsmiaError> {
+		let mut var = match name {
+			Expr::Variable(v) => v,
+			e => Variable::from_name(e.into()),
+		};
 		loop {
 			match self.get_current() {
 				Token::Dot => {
 					self.advance();
 					var.push(self.identifier()?.into());
 				},
-				Token::ArrayStart => {
+				Token::ArrayStart => var.push(self.array_selector()?),
+				Token::SafeDot => {
 					self.advance();
-					var.push(self.expr()?.into());
-					self.consume(Token::ArrayEnd, |parser| parser.error_msg(
-						ParserErrorMsg::Unclosed("array selector".to_string(), Token::ArrayEnd)
-					))?;
+					let key = match self.check_current(&Token::ArrayStart) {
+						true => self.array_selector()?,
+						false => self.identifier()?.into(),
+					};
+					var.push_safe(key);
 				},
 				_ => break
 			}
 		}
-		Ok(Variable::from_vec(var).into())
+		Ok(var.with_location(location).into())
+	}
+
+	fn array_selector(&mut self) -> Result<JsonTreeKeyExpr, OsmiaError> {
+		self.consume(Token::ArrayStart, |parser| parser.error_msg(
+			ParserErrorMsg::Expected(Token::ArrayStart)
+		))?;
+		let key = self.expr()?.into();
+		self.consume(Token::ArrayEnd, |parser| parser.error_msg(
+			ParserErrorMsg::Unclosed("array selector".to_string(), Token::ArrayEnd)
+		))?;
+		Ok(key)
 	}
 
 	fn identifier(&mut self) -> Result<JsonTreeKey<String>, OsmiaError> {
-		let key = match self.advance() {
+		let key = match self.get_current() {
 			Token::Alpha(s) => s.as_str().into(),
 			_ => return Err(self.error_msg(
 				ParserErrorMsg::Custom("Invalid identifier:".to_string())
 			)),
 		};
+		self.advance();
 		Ok(key)
 	}
 }
@@ -1,4 +1,3 @@
-use crate::OsmiaError;
 use crate::model::lexer::Token;
 use super::osmia_parser::OsmiaParserImpl;
 
@@ -8,19 +7,20 @@ pub enum ParserErrorMsg {
 	Unclosed(String, Token),
 	Expected(Token),
 	ParseValue(String),
+	InvalidParameter(String),
 }
 
 impl ParserErrorMsg {
-	pub fn report(&self, paser: &OsmiaParserImpl) -> OsmiaError {
+	pub fn report(&self, paser: &OsmiaParserImpl) -> String {
 		match self {
 			ParserErrorMsg::MissingKeyword(token) => format!(
 				"Expected keyword '{:?}' but got '{:?}'",
 				token, paser.get_current()
-			).into(),
+			),
 			ParserErrorMsg::Custom(msg) => format!(
 				"{} {:?}",
 				msg, paser.get_current()
-			).into(),
+			),
 			ParserErrorMsg::Unclosed(block_type, expected) => format!(
 				"Unclosed {}. Expected '{:?}' but got '{:?}'",
 				block_type, expected, paser.get_current()
@@ -33,6 +33,10 @@ impl ParserErrorMsg {
 				"Not able to parse {:?} as a {}",
 				paser.get_current(), parse_type
 			),
+			ParserErrorMsg::InvalidParameter(msg) => format!(
+				"Invalid parameter: {}",
+				msg
+			),
 		}
 	}
 }
@@ -4,11 +4,21 @@ use super::*;
 pub struct Assign {
 	var: Variable,
 	expr: Expr,
+	location: NodeLocation,
 }
 
 impl Assign {
 	pub fn new(var: Variable, expr: Expr) -> Self {
-		Self { var, expr }
+		Self { var, expr, location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
 	}
 
 	pub fn variable(&self) -> &Variable {
@@ -21,6 +21,10 @@ impl Block {
 	pub fn stmts(&self) -> &Vec<Stmt> {
 		&self.stmts
 	}
+
+	pub fn stmts_mut(&mut self) -> &mut Vec<Stmt> {
+		&mut self.stmts
+	}
 }
 
 impl From<Block> for Vec<Stmt> {
@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Capture {
+	var: Variable,
+	body: Box<Stmt>,
+	location: NodeLocation,
+}
+
+impl Capture {
+	pub fn new(var: Variable, body: Stmt) -> Self {
+		Self { var, body: Box::new(body), location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
+	pub fn variable(&self) -> &Variable {
+		&self.var
+	}
+
+	pub fn body(&self) -> &Stmt {
+		&self.body
+	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
+}
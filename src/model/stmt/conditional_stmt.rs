@@ -18,4 +18,8 @@ impl ConditionalStmt {
 	pub fn body(&self) -> &Stmt {
 		&self.body
 	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
 }
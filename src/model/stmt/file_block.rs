@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileBlock {
+	path: Expr,
+	body: Box<Stmt>,
+	location: NodeLocation,
+}
+
+impl FileBlock {
+	pub fn new(path: Expr, body: Stmt) -> Self {
+		Self { path, body: Box::new(body), location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
+	pub fn path(&self) -> &Expr {
+		&self.path
+	}
+
+	pub fn body(&self) -> &Stmt {
+		&self.body
+	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
+}
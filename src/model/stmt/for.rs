@@ -2,18 +2,46 @@ use super::*;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct For {
-	var: Variable,
+	key: Option<Pattern>,
+	pattern: Pattern,
 	iterable: Expr,
 	body: Box<Stmt>,
+	location: NodeLocation,
 }
 
 impl For {
-	pub fn new(var: Variable, iterable: Expr, body: Stmt) -> Self {
-		Self { var, iterable, body: Box::new(body) }
+	pub fn new(pattern: impl Into<Pattern>, iterable: Expr, body: Stmt) -> Self {
+		Self {
+			key: None,
+			pattern: pattern.into(),
+			iterable,
+			body: Box::new(body),
+			location: NodeLocation::default(),
+		}
 	}
 
-	pub fn variable(&self) -> &Variable {
-		&self.var
+	/// Stores the index of each item (or its key when iterating an object)
+	/// in the given pattern.
+	pub fn with_key(mut self, key: impl Into<Pattern>) -> Self {
+		self.key = Some(key.into());
+		self
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
+	pub fn key(&self) -> Option<&Pattern> {
+		self.key.as_ref()
+	}
+
+	pub fn pattern(&self) -> &Pattern {
+		&self.pattern
 	}
 
 	pub fn iterable(&self) -> &Expr {
@@ -23,4 +51,8 @@ impl For {
 	pub fn body(&self) -> &Stmt {
 		&self.body
 	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
 }
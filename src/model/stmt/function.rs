@@ -23,6 +23,10 @@ impl Function {
 	pub fn body(&self) -> &Stmt {
 		&self.body
 	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
 }
 
 impl std::fmt::Display for Function {
@@ -32,4 +32,16 @@ impl If {
 	pub fn else_block(&self) -> &Option<Box<Stmt>> {
 		&self._else
 	}
+
+	/// Bodies of all the branches of the statement.
+	pub fn bodies_mut(&mut self) -> Vec<&mut Stmt> {
+		let mut bodies = vec![self._if.body_mut()];
+		if let Some(elseifs) = &mut self._elseifs {
+			bodies.extend(elseifs.iter_mut().map(|e| e.body_mut()));
+		}
+		if let Some(else_block) = &mut self._else {
+			bodies.push(else_block);
+		}
+		bodies
+	}
 }
@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Include {
+	name: Expr,
+	location: NodeLocation,
+}
+
+impl Include {
+	pub fn new(name: Expr) -> Self {
+		Self { name, location: NodeLocation::default() }
+	}
+
+	pub fn with_location(mut self, location: Location) -> Self {
+		self.location = location.into();
+		self
+	}
+
+	pub fn location(&self) -> Option<Location> {
+		self.location.get()
+	}
+
+	pub fn name(&self) -> &Expr {
+		&self.name
+	}
+}
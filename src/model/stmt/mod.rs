@@ -1,9 +1,14 @@
 mod assign;
 mod block;
+mod capture;
 mod conditional_stmt;
+mod file_block;
 mod r#for;
 mod function;
 mod r#if;
+mod include;
+mod named_block;
+mod pattern;
 mod print;
 mod r#return;
 mod stmt;
@@ -12,10 +17,15 @@ mod r#while;
 
 pub use assign::Assign;
 pub use block::Block;
+pub use capture::Capture;
 pub use conditional_stmt::ConditionalStmt;
+pub use file_block::FileBlock;
 pub use r#for::For;
 pub use function::Function;
 pub use r#if::If;
+pub use include::Include;
+pub use named_block::NamedBlock;
+pub use pattern::Pattern;
 pub use print::Print;
 pub use r#return::Return;
 pub use stmt::Stmt;
@@ -23,3 +33,7 @@ pub use r#while::While;
 
 use super::expr::*;
 use crate::model::ctx::JsonTreeKey;
+use crate::model::error::{
+	Location,
+	NodeLocation,
+};
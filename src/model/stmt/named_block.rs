@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedBlock {
+	name: String,
+	body: Box<Stmt>,
+}
+
+impl NamedBlock {
+	pub fn new(name: &str, body: Stmt) -> Self {
+		Self { name: name.to_string(), body: Box::new(body) }
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn body(&self) -> &Stmt {
+		&self.body
+	}
+
+	pub fn body_mut(&mut self) -> &mut Stmt {
+		&mut self.body
+	}
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+/// Where the items of a [For] loop are stored.
+///
+/// Objects and arrays can be destructured into several variables.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+	/// `item`
+	Variable(Variable),
+	/// `{name, age: years}`: each key is stored in the variable of the same
+	/// name or in the given pattern.
+	Object(Vec<(String, Pattern)>),
+	/// `[first, second]`: the array must have as many items as patterns.
+	Array(Vec<Pattern>),
+}
+
+impl From<Variable> for Pattern {
+	fn from(var: Variable) -> Self {
+		Self::Variable(var)
+	}
+}
@@ -37,12 +37,15 @@ use super::*;
 ///
 /// # print
 /// Allows to print in the stdout the given [Expr](/osmia/#expression).
+/// The destination can be changed with a [PrintSink](crate::PrintSink).
 ///
 /// ```rust
-/// use osmia::Osmia;
+/// use osmia::{Osmia, PrintSink};
 ///
 /// let mut osmia = Osmia::default();
 /// osmia.run_code("{{print 1 + 1 }}").unwrap(); // prints "2"
+/// osmia.set_print_sink(PrintSink::Buffer);
+/// assert_eq!(osmia.run_code_with_prints("{{print 1 + 1 }}").unwrap().1, vec!["2"]);
 /// ```
 ///
 /// # comment
@@ -68,6 +71,35 @@ use super::*;
 /// }}"#).is_err());
 /// ```
 ///
+/// # raw
+/// Outputs its body exactly as it is written, without interpreting it.
+/// A single starting delimiter can also be escaped in the raw text with a backslash,
+/// while two backslashes print one and keep the statement.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::default();
+/// assert_eq!(
+///   osmia.run_code("{{raw}}Hello {{ name }}{{# }}{{endraw}}").unwrap(),
+///   "Hello {{ name }}{{# }}".to_string()
+/// );
+/// assert_eq!(osmia.run_code(r"\{{ 1 + 1 }} = {{ 1 + 1 }}").unwrap(), "{{ 1 + 1 }} = 2".to_string());
+/// assert_eq!(osmia.run_code(r"C:\\{{ 1 + 1 }}").unwrap(), r"C:\2".to_string());
+/// ```
+///
+/// # whitespace control
+/// A `-` next to a delimiter removes all the whitespace, including new lines, on that
+/// side of the statement. The starting marker `{{- ` must be followed by a whitespace.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::default();
+/// assert_eq!(osmia.run_code("a\n  {{- 1 + 1 -}}\n  b").unwrap(), "a2b".to_string());
+/// assert_eq!(osmia.run_code("[\n{{- for i in [1, 2] -}}\n  {{ i }},\n{{- done -}}\n]").unwrap(), "[1,2,]".to_string());
+/// ```
+///
 /// # assign
 /// Allows to assign an [Expr](/osmia/#expression) to a variable in the
 /// [Context](/osmia/#context).
@@ -124,6 +156,62 @@ use super::*;
 /// assert_eq!(osmia.run_code(r#"{{for i in [1, 2, 3]}}{{i}}{{done}}"#).unwrap(), "123".to_string());
 /// ```
 ///
+/// The iterable can be any expression resulting in an array, an object, a
+/// string (iterated by character) or a range. Ranges are iterated without
+/// building an array with all their items.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::try_from(r#"{"items": [1, 2, 3]}"#).unwrap();
+/// assert_eq!(osmia.run_code("{{for i in items?reverse()}}{{i}}{{done}}").unwrap(), "321");
+/// assert_eq!(osmia.run_code(r#"{{for c in "abc"}}{{c}} {{done}}"#).unwrap(), "a b c ");
+/// assert_eq!(osmia.run_code("{{for i in 1..=3}}{{i}}{{done}}").unwrap(), "123");
+/// assert_eq!(osmia.run_code("{{for i in (0..100)?step(25)?reverse()}}{{i}} {{done}}").unwrap(), "75 50 25 0 ");
+/// assert!(osmia.run_code("{{for i in 42}}{{i}}{{done}}").is_err());
+/// ```
+///
+/// Two variables can be given to also get the index of each item, or its key
+/// when iterating an object. Objects and arrays can be destructured too.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::try_from(r#"{
+///   "scores": {"Arthur": 42, "Ford": 7},
+///   "people": [{"name": "Arthur", "age": 42}, {"name": "Ford", "age": 200}]
+/// }"#).unwrap();
+/// assert_eq!(
+///   osmia.run_code("{{for name, score in scores}}{{name}}: {{score}}; {{done}}").unwrap(),
+///   "Arthur: 42; Ford: 7; "
+/// );
+/// assert_eq!(
+///   osmia.run_code("{{for i, {name, age: years} in people}}{{i}}.{{name}} {{years}} {{done}}").unwrap(),
+///   "0.Arthur 42 1.Ford 200 "
+/// );
+/// assert_eq!(osmia.run_code("{{for [a, b] in [[1, 2], [3, 4]]}}{{a * b}} {{done}}").unwrap(), "2 12 ");
+/// ```
+///
+/// Inside the loop, the `loop` variable holds the position of the current item:
+/// `index` (from 1), `index0` (from 0), `revindex`, `revindex0`, `first`, `last`,
+/// `length` and `outer`, the `loop` of the enclosing `for` (null if there is none).
+/// Any variable named `loop` shadows it, and it is not visible in the functions
+/// called from the loop.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::default();
+/// assert_eq!(
+///   osmia.run_code("{{for x in [1, 2, 3]}}{{x}}{{if !loop.last}}, {{fi}}{{done}}").unwrap(),
+///   "1, 2, 3"
+/// );
+/// assert_eq!(
+///   osmia.run_code("{{for r in [[1, 2], [3]]}}{{for c in r}}{{loop.outer.index}}.{{loop.index}} {{done}}{{done}}").unwrap(),
+///   "1.1 1.2 2.1 "
+/// );
+/// ```
+///
 /// # break
 /// Breaks out of the current [Block](#code--block).
 ///
@@ -192,6 +280,84 @@ use super::*;
 ///	assert_eq!(osmia.run_code(code).unwrap(), "3".to_string());
 /// ```
 ///
+/// # capture
+/// Renders the [Block](#code--block) storing its output in a variable instead of printing it.
+///
+/// The stored output is already escaped by the [EscapeMode](crate::EscapeMode),
+/// so printing the variable does not escape it again. Once the value is changed,
+/// like with `{{ toc + "." }}`, it is escaped as any other string.
+///
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let code = r#"
+/// {{capture toc}}
+/// {{for s in sections}}
+/// - {{ s }}
+/// {{done}}
+/// {{done}}
+/// {{ toc?len() }} characters:
+/// {{ toc }}
+/// "#.trim();
+/// let mut osmia = Osmia::try_from(r#"{ "sections": ["Intro", "End"] }"#).unwrap();
+/// assert_eq!(osmia.run_code(code).unwrap(), "14 characters:\n- Intro\n- End\n".to_string());
+/// ```
+///
+/// # file
+/// Renders the [Block](#code--block) into the file with the given path.
+/// The files are returned by [Osmia::render_files](crate::Osmia::render_files), where the
+/// rest of the output is stored under [MAIN_OUTPUT](crate::MAIN_OUTPUT).
+///
+/// ```rust
+/// use osmia::{Osmia, MAIN_OUTPUT};
+///
+/// let code = r#"
+/// {{for m in models}}
+/// {{file "src/" + m + ".rs"}}
+/// pub struct {{ m?capitalize() }};
+/// {{done}}
+/// mod {{ m }};
+/// {{done}}
+/// "#.trim();
+/// let mut osmia = Osmia::try_from(r#"{ "models": ["user", "post"] }"#).unwrap();
+/// let files = osmia.render_files(code).unwrap();
+/// assert_eq!(files[MAIN_OUTPUT], "mod user;\nmod post;\n");
+/// assert_eq!(files["src/user.rs"], "pub struct User;\n");
+/// assert_eq!(files["src/post.rs"], "pub struct Post;\n");
+/// ```
+///
+/// # include
+/// Renders the template with the given name, resolved by the
+/// [TemplateLoader](crate::TemplateLoader) of the engine, in the current context.
+///
+/// ```rust
+/// use osmia::{Osmia, MapLoader};
+///
+/// let loader = MapLoader::new()
+///   .add("item", "<li>{{ item }}</li>");
+/// let mut osmia = Osmia::default().with_loader(loader);
+/// let code = r#"{{for item in [1, 2]}}{{include "item"}}{{done}}"#;
+/// assert_eq!(osmia.run_code(code).unwrap(), "<li>1</li><li>2</li>".to_string());
+/// ```
+///
+/// # extends / block
+/// A template can extend another one, overriding some of its named blocks.
+/// The content of the overridden block is available with `parent`.
+/// Everything outside the blocks of the child template is ignored.
+///
+/// `extends`, `block` and `parent` are only keywords at the start of a
+/// statement, so they can still be used as variable names (`{{ parent.name }}`).
+///
+/// ```rust
+/// use osmia::{Osmia, MapLoader};
+///
+/// let loader = MapLoader::new()
+///   .add("base", "<h1>{{block title}}Osmia{{done}}</h1>{{block body}}{{done}}");
+/// let mut osmia = Osmia::default().with_loader(loader);
+/// let code = r#"{{extends "base"}}{{block title}}Docs - {{parent}}{{done}}"#;
+/// assert_eq!(osmia.run_code(code).unwrap(), "<h1>Docs - Osmia</h1>".to_string());
+/// ```
+///
 #[derive(Debug, PartialEq, Clone)]
 pub enum Stmt {
 	Block(Block),
@@ -210,6 +376,12 @@ pub enum Stmt {
 	Continue,
 	Return(Return),
 	Function(Function),
+	Include(Include),
+	Extends(String),
+	NamedBlock(NamedBlock),
+	Parent,
+	Capture(Capture),
+	FileBlock(FileBlock),
 }
 
 impl Stmt {
@@ -237,6 +409,34 @@ impl Stmt {
 	pub fn new_return(expr: Option<Expr>) -> Self {
 		Self::Return(Return::new(expr))
 	}
+
+	/// Statements directly nested in this one.
+	pub fn children_mut(&mut self) -> Vec<&mut Stmt> {
+		match self {
+			Stmt::Block(b) => b.stmts_mut().iter_mut().collect(),
+			Stmt::If(i) => i.bodies_mut(),
+			Stmt::While(w) => vec![w.body_mut()],
+			Stmt::For(f) => vec![f.body_mut()],
+			Stmt::Function(f) => vec![f.body_mut()],
+			Stmt::NamedBlock(b) => vec![b.body_mut()],
+			Stmt::Capture(c) => vec![c.body_mut()],
+			Stmt::FileBlock(f) => vec![f.body_mut()],
+			_ => vec![],
+		}
+	}
+
+	/// Position in the code of the statement, if it was parsed from it.
+	pub fn location(&self) -> Option<Location> {
+		match self {
+			Stmt::Expr(e) => e.location(),
+			Stmt::Assign(a) => a.location(),
+			Stmt::For(f) => f.location(),
+			Stmt::Include(i) => i.location(),
+			Stmt::Capture(c) => c.location(),
+			Stmt::FileBlock(f) => f.location(),
+			_ => None,
+		}
+	}
 }
 
 impl From<Block> for Stmt {
@@ -4,6 +4,10 @@ const len: BuiltinArg = |_, args| {
 	match &args[0] {
 		Expr::Str(s) => Ok(Expr::Int(s.len() as i64)),
 		Expr::Array(arr) => Ok(Expr::Int(arr.len() as i64)),
+		Expr::Range(r) => match i64::try_from(r.len()) {
+			Ok(length) => Ok(Expr::Int(length)),
+			Err(_) => Err(OsmiaError::runtime(format!("The length of the range {} does not fit in an int", r))),
+		},
 		_ => return Err("Cannot get length for this".into()),
 	}
 };
@@ -13,6 +17,7 @@ const has_content: BuiltinArg = |_, args| {
 		Expr::Str(s) => Ok(Expr::Bool(!s.is_empty())),
 		Expr::Array(arr) => Ok(Expr::Bool(arr.len() != 0)),
 		Expr::Object(obj) => Ok(Expr::Bool(obj.len() != 0)),
+		Expr::Range(r) => Ok(Expr::Bool(!r.is_empty())),
 		Expr::Int(_) | Expr::Float(_) => Ok(Expr::Bool(true)),
 		Expr::Bool(_) => Ok(Expr::Bool(true)),
 		Expr::Null => Ok(Expr::Bool(false)),
@@ -28,14 +33,14 @@ const to_bool: BuiltinArg = |_, args| {
 const to_float: BuiltinArg = |_, args| {
 	match &args[0].to_float() {
 		Ok(f) => Ok(Expr::Float(*f)),
-		Err(e) => Err(e.into()),
+		Err(e) => Err(e.clone()),
 	}
 };
 
 const to_int: BuiltinArg = |_, args| {
 	match &args[0].to_int() {
 		Ok(i) => Ok(Expr::Int(*i)),
-		Err(e) => Err(e.into()),
+		Err(e) => Err(e.clone()),
 	}
 };
 
@@ -118,6 +123,10 @@ const get: BuiltinArg = |_, args| {
 	}
 };
 
+const safe: BuiltinArg = |_, args| {
+	Ok(args[0].clone())
+};
+
 pub fn add_generics(module: Module) -> Module {
 	module
 	.add_value("len", Callable::new(
@@ -180,4 +189,9 @@ pub fn add_generics(module: Module) -> Module {
 		#[cfg(feature = "detailed-dumper")]
 		"Alternative to accessing by index or dot notation"
 	).into())
+	.add_value(SAFE_METHOD, Callable::new(
+		1, safe,
+		#[cfg(feature = "detailed-dumper")]
+		"Prints the variable without escaping it"
+	).into())
 }
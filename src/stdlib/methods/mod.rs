@@ -3,7 +3,8 @@ use generics::add_generics;
 use crate::OsmiaError;
 use crate::model::expr::{
 	Array,
-	Object
+	Object,
+	Range,
 };
 use crate::model::visitor_pattern::Visitor;
 
@@ -16,6 +17,7 @@ mod bool;
 mod null;
 mod array;
 mod object;
+mod range;
 mod callable;
 mod lambda;
 #[allow(dead_code)]
@@ -202,6 +204,26 @@ pub use utils::*;
 /// // There are no other methods
 /// ```
 ///
+/// ### Range:
+/// ```rust
+/// use osmia::Osmia;
+///
+/// let mut osmia = Osmia::default();
+///
+/// // Generics
+/// assert_eq!(osmia.run_code(r#"{{ (0..10)?len() }}"#).unwrap(), "10");
+/// assert_eq!(osmia.run_code(r#"{{ (0..0)?has_content() }}"#).unwrap(), "false");
+/// assert_eq!(osmia.run_code(r#"{{ (0..=3)?to_string() }}"#).unwrap(), "0..4");
+/// assert_eq!(osmia.run_code(r#"{{ (0..3)?type() }}"#).unwrap(), "range");
+///
+/// // Range only
+/// assert_eq!(osmia.run_code(r#"{{ (0..10)?step(3) }}"#).unwrap(), "(0..10)?step(3)");
+/// assert_eq!(osmia.run_code(r#"{{ (0..10)?step(3)?len() }}"#).unwrap(), "4");
+/// assert_eq!(osmia.run_code(r#"{{ (0..3)?reverse() }}"#).unwrap(), "(0..3)?reverse()");
+/// assert_eq!(osmia.run_code(r#"{{ (0..10)?step(2)?contains(4) }}"#).unwrap(), "true");
+/// assert_eq!(osmia.run_code(r#"{{ (0..10)?step(2)?contains(5) }}"#).unwrap(), "false");
+/// ```
+///
 /// ## Sub modules:
 /// There are no sub modules for this module
 pub fn module() -> Module {
@@ -234,6 +256,10 @@ pub fn module() -> Module {
 		MethodExpression::Object.into(),
 		add_generics(object::module())
 	)
+	.add_module(
+		MethodExpression::Range.into(),
+		add_generics(range::module())
+	)
 	.add_module(
 		MethodExpression::Callable.into(),
 		add_generics(callable::module())
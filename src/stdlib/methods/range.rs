@@ -0,0 +1,26 @@
+use super::*;
+
+pub fn module() -> Module {
+	Module::new()
+	.add_value("step", Callable::new(2,
+		|_, args| Ok(range_or_fail(&args[0])?.step(int_or_fail(&args[1])?)?.into()),
+		#[cfg(feature = "detailed-dumper")]
+		"Takes one of every n items of the range"
+	).into())
+	.add_value("reverse", Callable::new(1,
+		|_, args| Ok(range_or_fail(&args[0])?.reverse().into()),
+		#[cfg(feature = "detailed-dumper")]
+		"Reverses the range"
+	).into())
+	.add_value("contains", Callable::new(2,
+		|_, args| {
+			let range = range_or_fail(&args[0])?;
+			Ok(Expr::Bool(match &args[1] {
+				Expr::Int(i) => range.contains(*i),
+				_ => false,
+			}))
+		},
+		#[cfg(feature = "detailed-dumper")]
+		"Checks if the value is one of the items of the range"
+	).into())
+}
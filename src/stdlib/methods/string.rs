@@ -102,7 +102,7 @@ pub fn module() -> Module {
 			let pattern = string_or_fail(&args[1])?;
 			let re = match Regex::new(pattern) {
 				Ok(re) => re,
-				Err(e) => return Err(format!("Invalid regex: {}", e))
+				Err(e) => return Err(format!("Invalid regex: {}", e).into())
 			};
 			Ok(Expr::Bool(re.is_match(&s)))
 		},
@@ -114,7 +114,7 @@ pub fn module() -> Module {
 			let s = string_or_fail(&args[0])?;
 			let pattern = match Regex::new(string_or_fail(&args[1])?) {
 				Ok(re) => re,
-				Err(e) => return Err(format!("Invalid regex: {}", e))
+				Err(e) => return Err(format!("Invalid regex: {}", e).into())
 			};
 			let repl = string_or_fail(&args[2])?;
 			Ok(Expr::Str(pattern.replace(&s, repl).into()))
@@ -127,7 +127,7 @@ pub fn module() -> Module {
 			let s = string_or_fail(&args[0])?;
 			let pattern = match Regex::new(string_or_fail(&args[1])?) {
 				Ok(re) => re,
-				Err(e) => return Err(format!("Invalid regex: {}", e))
+				Err(e) => return Err(format!("Invalid regex: {}", e).into())
 			};
 			let repl = string_or_fail(&args[2])?;
 			Ok(Expr::Str(pattern.replace_all(&s, repl).into()))
@@ -151,7 +151,7 @@ pub fn module() -> Module {
 			let start = usize_or_fail(&args[1])?;
 			let end = usize_or_fail(&args[2])?;
 			if start > end {
-				return Err(format!("Cannot start after end: {} > {}", start, end));
+				return Err(format!("Cannot start after end: {} > {}", start, end).into());
 			}
 			Ok(Expr::Str(s[start..end].into()))
 		},
@@ -6,7 +6,10 @@ use crate::model::ctx::{
 	lib::Module,
 };
 use crate::model::interpreter::Callable;
-use crate::constants::METHOD_CTX_LOCATION;
+use crate::constants::{
+	METHOD_CTX_LOCATION,
+	SAFE_METHOD,
+};
 use crate::model::interpreter::MethodExpression;
 use crate::model::interpreter::callable::BuiltinArg;
 
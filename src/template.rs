@@ -0,0 +1,175 @@
+use std::rc::Rc;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::types::{
+	Ctx,
+	OsmiaError,
+	OsmiaOutput,
+	ParserCode,
+};
+use crate::model::interpreter::{
+	OsmiaInterpreter,
+	Limits,
+	EscapeMode,
+	PrintSink,
+};
+use crate::model::loader::{
+	TemplateCache,
+	TemplateLoader,
+};
+use crate::model::lexer::Syntax;
+use crate::constants::MAIN_OUTPUT;
+
+/// Code already lexed, parsed and resolved by [Osmia::compile](crate::Osmia::compile).
+///
+/// It can be rendered as many times as needed against different contexts
+/// without paying the cost of parsing it again. The included templates are
+/// loaded and parsed the first time they are rendered, and reused after that.
+///
+/// ```rust
+/// use osmia::{Osmia, Ctx};
+///
+/// let template = Osmia::default().compile("Hello {{ name }}!").unwrap();
+/// assert_eq!(template.render_with(r#"{ "name": "Marvin" }"#).unwrap(), "Hello Marvin!");
+/// assert_eq!(template.render_with(r#"{ "name": "R2D2" }"#).unwrap(), "Hello R2D2!");
+///
+/// let mut ctx = Ctx::try_from(r#"{ "name": "C3PO" }"#).unwrap();
+/// assert_eq!(template.render(&mut ctx).unwrap(), "Hello C3PO!");
+/// ```
+pub struct Template {
+	code: String,
+	parsed: ParserCode,
+	loader: Option<Rc<dyn TemplateLoader>>,
+	includes: TemplateCache,
+	limits: Limits,
+	escape: EscapeMode,
+	syntax: Syntax,
+	print_sink: PrintSink,
+}
+
+impl Template {
+	pub(crate) fn new(
+		code: &str,
+		parsed: ParserCode,
+		loader: Option<Rc<dyn TemplateLoader>>,
+		limits: Limits,
+		escape: EscapeMode,
+		syntax: Syntax,
+		print_sink: PrintSink,
+	) -> Self {
+		Self {
+			code: code.to_string(),
+			parsed,
+			loader,
+			includes: TemplateCache::new(),
+			limits,
+			escape,
+			syntax,
+			print_sink,
+		}
+	}
+
+	fn interpreter<'a>(&'a self, ctx: &'a mut Ctx) -> OsmiaInterpreter<'a> {
+		OsmiaInterpreter::new(ctx)
+			.with_loader(self.loader.as_deref())
+			.with_template_cache(&self.includes)
+			.with_limits(self.limits)
+			.with_escape(self.escape.clone())
+			.with_syntax(self.syntax.clone())
+			.with_print_sink(self.print_sink.clone())
+	}
+
+	/// Renders the template using the given context.
+	///
+	/// The variables set by the template are stored in the context.
+	pub fn render(&self, ctx: &mut Ctx) -> Result<OsmiaOutput, OsmiaError> {
+		self.interpreter(ctx)
+			.interpret_code(&self.parsed)
+			.map_err(|e| e.with_code_trace(&self.code))
+	}
+
+	/// Renders the template returning the content of every file block by path,
+	/// with the rest of the output under [MAIN_OUTPUT](crate::MAIN_OUTPUT).
+	pub fn render_files(&self, ctx: &mut Ctx) -> Result<BTreeMap<String, String>, OsmiaError> {
+		let interpreter = self.interpreter(ctx);
+		let output = interpreter.interpret_code(&self.parsed)
+			.map_err(|e| e.with_code_trace(&self.code))?;
+		let mut files = interpreter.take_files();
+		files.insert(MAIN_OUTPUT.into(), output);
+		Ok(files)
+	}
+
+	/// Renders the template returning, along with the output, the values
+	/// printed when using [PrintSink::Buffer].
+	pub fn render_with_prints(&self, ctx: &mut Ctx) -> Result<(OsmiaOutput, Vec<String>), OsmiaError> {
+		let interpreter = self.interpreter(ctx);
+		let output = interpreter.interpret_code(&self.parsed)
+			.map_err(|e| e.with_code_trace(&self.code))?;
+		Ok((output, interpreter.take_printed()))
+	}
+
+	/// Renders the template writing the output into `writer` as it is generated,
+	/// instead of building it in memory.
+	///
+	/// ```rust
+	/// use osmia::{Osmia, Ctx};
+	///
+	/// let template = Osmia::default().compile("{{for i in [1, 2, 3]}}{{ i }}{{done}}").unwrap();
+	/// let mut output: Vec<u8> = Vec::new();
+	/// template.render_to(&mut Ctx::new(), &mut output).unwrap();
+	/// assert_eq!(output, b"123");
+	/// ```
+	pub fn render_to(&self, ctx: &mut Ctx, writer: &mut impl Write) -> Result<(), OsmiaError> {
+		self.interpreter(ctx)
+			.with_writer(writer)
+			.render_code(&self.parsed)
+			.map_err(|e| e.with_code_trace(&self.code))
+	}
+
+	/// Same as [Template::render_to], returning the values printed when using
+	/// [PrintSink::Buffer].
+	pub fn render_to_with_prints(&self, ctx: &mut Ctx, writer: &mut impl Write) -> Result<Vec<String>, OsmiaError> {
+		let interpreter = self.interpreter(ctx).with_writer(writer);
+		interpreter.render_code(&self.parsed)
+			.map_err(|e| e.with_code_trace(&self.code))?;
+		Ok(interpreter.take_printed())
+	}
+
+	/// Renders the template using a new context with the given JSON content.
+	pub fn render_with(&self, json: &str) -> Result<OsmiaOutput, OsmiaError> {
+		self.render(&mut Ctx::try_from(json)?)
+	}
+
+	/// Replaces the [Limits] taken from the [Osmia](crate::Osmia) instance that compiled it.
+	pub fn with_limits(mut self, limits: Limits) -> Self {
+		self.limits = limits;
+		self
+	}
+
+	/// Replaces the [EscapeMode] taken from the [Osmia](crate::Osmia) instance that compiled it.
+	///
+	/// ```rust
+	/// use osmia::{Osmia, EscapeMode};
+	///
+	/// let template = Osmia::default().compile("echo {{ msg }}").unwrap();
+	/// let json = r#"{ "msg": "it's $HOME" }"#;
+	/// assert_eq!(template.render_with(json).unwrap(), "echo it's $HOME");
+	/// let template = template.with_escape(EscapeMode::Shell);
+	/// assert_eq!(template.render_with(json).unwrap(), r#"echo 'it'\''s $HOME'"#);
+	/// ```
+	pub fn with_escape(mut self, escape: EscapeMode) -> Self {
+		self.escape = escape;
+		self
+	}
+
+	/// Replaces the [PrintSink] taken from the [Osmia](crate::Osmia) instance that compiled it.
+	pub fn with_print_sink(mut self, print_sink: PrintSink) -> Self {
+		self.print_sink = print_sink;
+		self
+	}
+
+	pub fn code(&self) -> &str {
+		&self.code
+	}
+}
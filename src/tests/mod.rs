@@ -3,6 +3,7 @@ mod osmia;
 mod utils;
 
 use crate::Osmia;
+use crate::model::lexer::Token;
 use crate::CodeInterpreter;
 use crate::types::{
 	Ctx,
@@ -12,7 +13,7 @@ use crate::types::{
 #[cfg(test)]
 fn test_code(
 	code: &str,
-	tokens: Option<<Osmia as CodeInterpreter>::LexerCode>,
+	tokens: Option<Vec<Token>>,
 	parsed: Option<<Osmia as CodeInterpreter>::ParserCode>,
 	execution: Vec<(Ctx, Result<&str, Vec<&str>>)>
 ) {
@@ -23,7 +24,7 @@ fn test_code(
 #[cfg(test)]
 fn test(
 	code: Option<&str>,
-	tokens: Option<<Osmia as CodeInterpreter>::LexerCode>,
+	tokens: Option<Vec<Token>>,
 	parsed: Option<<Osmia as CodeInterpreter>::ParserCode>,
 	execution: Option<Vec<(Ctx, Result<&str, Vec<&str>>)>>
 ) {
@@ -49,7 +50,7 @@ fn test(
 			}
 			assert_eq!(lexed_code, &tokens);
 		},
-		(None, Some(tokens)) => lexed_code = Some(tokens.to_vec()),
+		(None, Some(tokens)) => lexed_code = Some(tokens.into_iter().map(|t| t.into()).collect()),
 		_ => ()
 	};
 	// Parsing
@@ -88,7 +89,7 @@ fn test(
 					Err(error_pieces) => match result {
 						Err(err) => {
 							println!("The code can not be executed:\n{}", err);
-							let err = err.to_lowercase();
+							let err = err.to_string().to_lowercase();
 							for piece in error_pieces {
 								println!("  - {}", piece);
 								assert!(err.contains(piece.to_lowercase().as_str()));
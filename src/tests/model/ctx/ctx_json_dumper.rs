@@ -4,7 +4,10 @@ use crate::model::ctx::{
 	JsonTree
 };
 use crate::macro_tests;
-use crate::Osmia;
+use crate::{
+	Osmia,
+	OsmiaError,
+};
 
 #[cfg(test)]
 fn format_node(t: &str, v: &str) -> String {
@@ -98,7 +101,7 @@ macro_tests!(
 );
 
 #[cfg(test)]
-fn osmia_json_variable_dump(variable: &str, json: &str) -> Result<String, String> {
+fn osmia_json_variable_dump(variable: &str, json: &str) -> Result<String, OsmiaError> {
 	println!("variable: {}", variable);
 	println!("json: {}", json);
 	let ctx: JsonTree<String, CtxValue> = serde_json::from_str(json).unwrap();
@@ -119,7 +122,7 @@ fn test_invalid_variable_value(v: &str, expected: Vec<&str>) {
 	let dump = osmia.ctx_json_dump_variable(v);
 	println!("Should be an error: {}", v);
 	assert!(dump.is_err());
-	let error = dump.unwrap_err().to_lowercase();
+	let error = dump.unwrap_err().to_string().to_lowercase();
 	println!("Error: {}", error);
 	for e in expected {
 		println!("Expected: {}", e);
@@ -21,7 +21,7 @@ fn get_expr(intpr: &OsmiaInterpreter<'_>, key: &str) -> Result<Expr, OsmiaError>
 fn get_ft(intpr: &OsmiaInterpreter<'_>, key: &str) -> Result<Callable, OsmiaError> {
 	match intpr.ctx.borrow().get(&JsonTreeKey::try_parse(key)?) {
 		Ok(JsonTree::Value(CtxValue::Callable(c))) => Ok(c.clone()),
-		Ok(_) => Err(format!("Not a callable")),
+		Ok(_) => Err("Not a callable".into()),
 		Err(e) => Err(e),
 	}
 }
@@ -45,7 +45,7 @@ fn test_constant(
 	let intpr = OsmiaInterpreter::new(&mut ctx);
 	match (get_expr(&intpr, key), expected) {
 		(Ok(expr), Ok(expected)) => assert_eq!(expr, expected),
-		(Err(err), Err(error_pieces)) => check_pieces(&err, error_pieces),
+		(Err(err), Err(error_pieces)) => check_pieces(&err.to_string(), error_pieces),
 		(r, e) => panic!("Constant should return {:?} but returned {:?}", e, r),
 	}
 }
@@ -62,12 +62,12 @@ fn test_callable(
 		Ok(f) => f,
 		Err(err) => match expected {
 			Ok(expr) => panic!("Callable should return {:?} but returned {:?}", expr, err),
-			Err(error_pieces) => return check_pieces(&err, error_pieces),
+			Err(error_pieces) => return check_pieces(&err.to_string(), error_pieces),
 		},
 	};
 	match (ft.call(&intpr, &args), expected) {
 		(Ok(expr), Ok(expected)) => assert_eq!(expr, expected),
-		(Err(err), Err(error_pieces)) => check_pieces(&err, error_pieces),
+		(Err(err), Err(error_pieces)) => check_pieces(&err.to_string(), error_pieces),
 		(r, e) => panic!("Callable should return {:?} but returned {:?}", e, r),
 	}
 }
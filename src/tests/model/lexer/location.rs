@@ -0,0 +1,73 @@
+use super::*;
+
+#[cfg(test)]
+fn lexer_location_test(
+	code: &str,
+	expected: Vec<(Token, usize, usize)>,
+) {
+	let tokens = OsmiaLexer::new().lex(code).unwrap();
+	let tokens: Vec<(Token, usize, usize)> = tokens.iter().map(|t| (
+		t.token().clone(), t.location().line(), t.location().column()
+	)).collect();
+	println!("real    : {:?}", tokens);
+	println!("expected: {:?}", expected);
+	assert_eq!(tokens, expected);
+}
+
+macro_tests!(
+	lexer_location_test,
+	(
+		location01,
+		"{{ x }}",
+		vec![
+			(Token::StmtStart, 1, 1),
+			(Token::Whitespace, 1, 3),
+			(Token::new_alpha("x"), 1, 4),
+			(Token::Whitespace, 1, 5),
+			(Token::StmtEnd, 1, 6),
+			(Token::Eof, 1, 8),
+		]
+	),
+	(
+		location02,
+		"Hi\n\t{{ 12 >= \"a\" }}",
+		vec![
+			(Token::new_raw("Hi"), 1, 1),
+			(Token::NewLine, 1, 3),
+			(Token::new_raw("\t"), 2, 1),
+			(Token::StmtStart, 2, 2),
+			(Token::Whitespace, 2, 4),
+			(Token::new_number("12"), 2, 5),
+			(Token::Whitespace, 2, 7),
+			(Token::GreaterEqual, 2, 8),
+			(Token::Whitespace, 2, 10),
+			(Token::new_str("a"), 2, 11),
+			(Token::Whitespace, 2, 14),
+			(Token::StmtEnd, 2, 15),
+			(Token::Eof, 2, 17),
+		]
+	),
+	(
+		location03,
+		"{{# a\nb }}{{ {\"k\": 1} }}",
+		vec![
+			(Token::StmtStart, 1, 1),
+			(Token::Comment, 1, 3),
+			(Token::new_raw(" a"), 1, 4),
+			(Token::NewLineNonPrintable, 1, 6),
+			(Token::new_raw("b "), 2, 1),
+			(Token::StmtEnd, 2, 3),
+			(Token::StmtStart, 2, 5),
+			(Token::Whitespace, 2, 7),
+			(Token::ObjectStart, 2, 8),
+			(Token::new_str("k"), 2, 9),
+			(Token::Colon, 2, 12),
+			(Token::Whitespace, 2, 13),
+			(Token::new_number("1"), 2, 14),
+			(Token::ObjectEnd, 2, 15),
+			(Token::Whitespace, 2, 16),
+			(Token::StmtEnd, 2, 17),
+			(Token::Eof, 2, 19),
+		]
+	),
+);
@@ -1,9 +1,11 @@
+mod location;
 mod token;
 mod raw;
 mod number;
 mod simple_token;
 mod string;
 mod variable;
+mod syntax;
 
 use crate::macro_tests;
 use crate::model::lexer::{
@@ -53,7 +55,7 @@ fn lexer_test_fail(
 		Err(err) => {
 			println!("Error: {}", err);
 			println!("Expected piece: {}", piece_error);
-			assert!(err.to_lowercase().contains(piece_error));
+			assert!(err.to_string().to_lowercase().contains(piece_error));
 		}
 	}
 }
@@ -12,12 +12,13 @@ macro_tests!(
 	(number_08, "123456789123456789", vec![Token::new_number("123456789123456789")]),
 	(number_09, "123456789123456789.123456789123456789", vec![Token::new_number("123456789123456789.123456789123456789")]),
 	(number_10, "123\n23\t1", vec![Token::new_number("123"), Token::NewLine, Token::new_number("23"), Token::Whitespace, Token::new_number("1")]),
+	(number_11, "1..10", vec![Token::new_number("1"), Token::Range, Token::new_number("10")]),
+	(number_12, "1.5..=2.5", vec![Token::new_number("1.5"), Token::RangeInclusive, Token::new_number("2.5")]),
 );
 
 macro_tests!(
 	lexer_expression_test_fail,
 	(invalid_number_01, "123.", "expected num"),
-	(invalid_number_02, "123..", "expected num"),
 	(invalid_number_03, "123.2.", "dot"),
 	(invalid_number_04, "123.2.3", "dot"),
 );
@@ -32,6 +32,16 @@ macro_tests!(
 		Token::Colon, Token::Whitespace, Token::Semicolon, Token::Whitespace,
 		Token::Spread, Token::Whitespace, Token::Question, Token::Whitespace, Token::Arrow,
 	]),
+	(simple_tokens_null_safe, "?? ?. ? ??? a?.b", vec![
+		Token::NullCoalesce, Token::Whitespace, Token::SafeDot, Token::Whitespace,
+		Token::Question, Token::Whitespace, Token::NullCoalesce, Token::Question, Token::Whitespace,
+		Token::new_alpha("a"), Token::SafeDot, Token::new_alpha("b"),
+	]),
+	(simple_tokens_range, ".. ..= ... a..b", vec![
+		Token::Range, Token::Whitespace, Token::RangeInclusive, Token::Whitespace,
+		Token::Spread, Token::Whitespace,
+		Token::new_alpha("a"), Token::Range, Token::new_alpha("b"),
+	]),
 	(keyword_tokens, "print fn return if elseif else fi while for in continue break done true false null", vec![
 		Token::Print, Token::Whitespace,
 		Token::Function, Token::Whitespace, Token::Return, Token::Whitespace,
@@ -40,6 +50,20 @@ macro_tests!(
 		Token::Continue, Token::Whitespace, Token::Break, Token::Whitespace, Token::Done, Token::Whitespace,
 		Token::Bool(true), Token::Whitespace, Token::Bool(false), Token::Whitespace, Token::Null
 	]),
+	(keyword_tokens_include, "include \"a\"", vec![Token::Include, Token::Whitespace, Token::new_str("a")]),
+	(keyword_tokens_include_variable, "include", vec![Token::new_alpha("include")]),
+	(keyword_tokens_file, "file \"a\"", vec![Token::FileBlock, Token::Whitespace, Token::new_str("a")]),
+	(keyword_tokens_file_assign, "file = 1", vec![
+		Token::new_alpha("file"), Token::Whitespace, Token::Assign, Token::Whitespace, Token::new_number("1"),
+	]),
+	(keyword_tokens_extends, "extends block parent", vec![
+		Token::Extends, Token::Whitespace, Token::new_alpha("block"), Token::Whitespace, Token::new_alpha("parent"),
+	]),
+	(keyword_tokens_block, "block parent", vec![Token::Block, Token::Whitespace, Token::new_alpha("parent")]),
+	(keyword_tokens_parent, "parent", vec![Token::Parent]),
+	(keyword_tokens_parent_variable, "parent.block", vec![
+		Token::new_alpha("parent"), Token::Dot, Token::new_alpha("block"),
+	]),
 );
 
 macro_tests!(
@@ -0,0 +1,82 @@
+use super::*;
+use crate::model::lexer::Syntax;
+
+#[cfg(test)]
+fn syntax_test(
+	syntax: Syntax,
+	code: &str,
+	mut tokens: Vec<Token>
+) {
+	tokens.push(Token::Eof);
+	let lexed = OsmiaLexer::new().with_syntax(syntax).lex(code).unwrap();
+	let lexed: Vec<Token> = lexed.into_iter().map(|t| t.token().clone()).collect();
+	assert_eq!(lexed, tokens);
+}
+
+#[cfg(test)]
+fn syntax_test_fail(
+	syntax: Syntax,
+	code: &str,
+	piece_error: &str
+) {
+	match OsmiaLexer::new().with_syntax(syntax).lex(code) {
+		Ok(t) => panic!("Should fail but gave: {:?}", t),
+		Err(err) => assert!(
+			err.to_string().contains(piece_error),
+			"{:?} does not contain {:?}", err.to_string(), piece_error
+		),
+	}
+}
+
+macro_tests!(
+	syntax_test,
+	(erb, Syntax::new("<%", "%>"), "{{ a }}<% a %>", vec![
+		Token::new_raw("{{ a }}"),
+		Token::StmtStart, Token::Whitespace, Token::new_alpha("a"), Token::Whitespace, Token::StmtEnd,
+	]),
+	(erb_operators, Syntax::new("<%", "%>"), "<%a%b<c%>", vec![
+		Token::StmtStart,
+		Token::new_alpha("a"), Token::Mod, Token::new_alpha("b"), Token::Less, Token::new_alpha("c"),
+		Token::StmtEnd,
+	]),
+	(brackets, Syntax::new("[[", "]]"), "[[ a[b[0]]]]", vec![
+		Token::StmtStart, Token::Whitespace,
+		Token::new_alpha("a"), Token::ArrayStart,
+		Token::new_alpha("b"), Token::ArrayStart, Token::new_number("0"), Token::ArrayEnd,
+		Token::ArrayEnd,
+		Token::StmtEnd,
+	]),
+	(brackets_object, Syntax::new("[[", "]]"), "[[{}]]", vec![
+		Token::StmtStart, Token::ObjectStart, Token::ObjectEnd, Token::StmtEnd,
+	]),
+	(parentheses, Syntax::new("((", "))"), "((f(g())))", vec![
+		Token::StmtStart,
+		Token::new_alpha("f"), Token::ParentStart, Token::new_alpha("g"), Token::ParentStart, Token::ParentEnd,
+		Token::ParentEnd,
+		Token::StmtEnd,
+	]),
+	(single_char, Syntax::new("$", "$"), "a $b$ c", vec![
+		Token::new_raw("a "),
+		Token::StmtStart, Token::new_alpha("b"), Token::StmtEnd,
+		Token::new_raw(" c"),
+	]),
+	(comment, Syntax::new("<%", "%>").with_comment("--"), "<%-- a <% b %> c %>", vec![
+		Token::StmtStart, Token::Comment, Token::new_raw(" a <% b %> c "), Token::StmtEnd,
+	]),
+	(comment_default_marker, Syntax::new("<%", "%>"), "<%# a %>", vec![
+		Token::StmtStart, Token::Comment, Token::new_raw(" a "), Token::StmtEnd,
+	]),
+	(comment_marker_only, Syntax::default().with_comment("!"), "{{! b }}", vec![
+		Token::StmtStart, Token::Comment, Token::new_raw(" b "), Token::StmtEnd,
+	]),
+);
+
+macro_tests!(
+	syntax_test_fail,
+	(unclosed, Syntax::new("<%", "%>"), "<% a", "Expected '%>'"),
+	(unclosed_array, Syntax::new("[[", "]]"), "[[ a[0 ]]", "Expected ']]'"),
+	(replaced_comment_marker, Syntax::default().with_comment("!"), "{{# a }}", "Unexpected token"),
+	(empty_start, Syntax::new("", "}}"), "{{ a }}", "cannot be empty"),
+	(empty_end, Syntax::new("{{", ""), "{{ a }}", "cannot be empty"),
+	(empty_comment, Syntax::default().with_comment(""), "{{ a }}", "cannot be empty"),
+);
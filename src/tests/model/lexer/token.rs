@@ -52,6 +52,10 @@ macro_tests! {
 	(debug_token_comment, Token::Comment, "#"),
 	(debug_token_function, Token::Function,"fn"),
 	(debug_token_return, Token::Return,"return"),
+	(debug_token_include, Token::Include,"include"),
+	(debug_token_extends, Token::Extends,"extends"),
+	(debug_token_block, Token::Block,"block"),
+	(debug_token_parent, Token::Parent,"parent"),
 
 	// Conditionals
 	(debug_token_if, Token::If,"if"),
@@ -108,6 +112,10 @@ macro_tests! {
 	(debug_token_colon, Token::Colon,":"),
 	(debug_token_semicolon, Token::Semicolon,";"),
 	(debug_token_spread, Token::Spread,"..."),
+	(debug_token_range, Token::Range,".."),
+	(debug_token_range_inclusive, Token::RangeInclusive,"..="),
 	(debug_token_question, Token::Question,"?"),
+	(debug_token_null_coalesce, Token::NullCoalesce,"??"),
+	(debug_token_safe_dot, Token::SafeDot,"?."),
 	(debug_token_arrow, Token::Arrow, "=>"),
 }
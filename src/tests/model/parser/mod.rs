@@ -15,11 +15,11 @@ fn parser_test_fail_tokens(
 	code: Vec<Token>,
 	error_pieces: &[&str],
 ) {
-	match Osmia::parse(code) {
+	match Osmia::parse(code.into_iter().map(|t| t.into()).collect()) {
 		Ok(expr) => panic!("The code should not be parsed: {:?}", expr),
 		Err(err) => {
 			println!("Error: {}", err);
-			let err = err.to_lowercase();
+			let err = err.to_string().to_lowercase();
 			for error_piece in error_pieces {
 				println!("Looking for: {}", error_piece);
 				assert!(err.contains(error_piece));
@@ -36,6 +36,6 @@ fn parser_test_fail(
 	let lexed = Osmia::lex(code).unwrap_or_else(|err| {
 		panic!("The code can not be lexed: {}", err)
 	});
-	parser_test_fail_tokens(lexed, error_pieces);
+	parser_test_fail_tokens(lexed.into_iter().map(|t| t.into()).collect(), error_pieces);
 }
 
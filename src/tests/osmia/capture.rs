@@ -0,0 +1,132 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		capture_01,
+		Some("{{capture x}}a{{ 1 }}{{done}}{{ x }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Capture,
+			Token::Whitespace,
+			Token::new_alpha("x"),
+			Token::StmtEnd,
+			Token::new_raw("a"),
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_number("1"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::StmtStart,
+			Token::Done,
+			Token::StmtEnd,
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("x"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::Block(vec![
+			Stmt::Capture(Capture::new(
+				Variable::from_vec(vec![
+					JsonTreeKeyExpr::JsonTreeKey("x".into())
+				]),
+				Stmt::Block(vec![
+					Stmt::new_raw("a"),
+					Expr::Int(1).into(),
+				].into()),
+			)),
+			Expr::Variable(Variable::from_vec(vec![
+					JsonTreeKeyExpr::JsonTreeKey("x".into())
+				])).into(),
+		].into())),
+		Some(vec![(Ctx::new(), Ok("a1"))])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		capture_empty,
+		"{{capture x}}{{done}}[{{ x }}]",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("[]"))]
+	),
+	(
+		capture_nested_variable,
+		"{{capture page.toc}}{{for s in sections}}{{ s }};{{done}}{{done}}{{ page.toc?upper() }} {{ page.title }}",
+		None,
+		None,
+		vec![(
+			Ctx::try_from(r#"{"page": {"title": "Docs"}, "sections": ["a", "b"]}"#).unwrap(),
+			Ok("A;B; Docs")
+		)]
+	),
+	(
+		capture_as_variable,
+		"{{ capture }} {{capture.len}} {{capture capture}}x{{done}}{{ capture }} {{for capture in [1, 2]}}{{ capture }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"capture": {"len": 3}}"#).unwrap(), Ok("{\"len\": 3} 3 x 12"))]
+	),
+	(
+		capture_nested,
+		"{{capture a}}1{{capture b}}2{{done}}3{{ b }}{{done}}{{ a }}{{ b }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1322"))]
+	),
+	(
+		capture_before_output,
+		"{{capture body}}\n{{for i in [1, 2]}}\n<p>{{ i }}</p>\n{{done}}\n{{done}}\n{{ body?len() }}\n{{ body }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("18\n<p>1</p>\n<p>2</p>\n"))]
+	),
+	(
+		capture_break,
+		"{{for i in [1, 2, 3]}}{{capture x}}{{ i }}{{if i == 2}}{{break}}{{fi}}!{{done}}{{done}}{{ x }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("2"))]
+	),
+	(
+		capture_return,
+		"{{fn f}}{{capture x}}a{{return 1}}{{done}}{{done}}{{ f() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1"))]
+	),
+	(
+		capture_function_output,
+		"{{fn f; x}}<{{ x }}>{{done}}{{capture y}}{{ f(1) }}{{ f(2) }}{{done}}{{ y }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("<1><2>"))]
+	),
+);
+
+#[test]
+fn capture_escaped() {
+	let mut osmia = crate::Osmia::try_from(r#"{"name": "<b>"}"#).unwrap()
+		.with_escape(crate::EscapeMode::Html);
+	assert_eq!(
+		osmia.run_code("{{capture c}}<p>{{ name }}</p>{{done}}{{ c }} {{ c?safe() }}").unwrap(),
+		"<p>&lt;b&gt;</p> <p>&lt;b&gt;</p>"
+	);
+	assert_eq!(
+		osmia.run_code("{{capture c}}<i>{{done}}{{ c = c + name }}{{ c }} {{ c?upper() }}").unwrap(),
+		"&lt;i&gt;&lt;b&gt; &lt;I&gt;&lt;B&gt;"
+	);
+}
+
+#[test]
+fn capture_errors() {
+	let mut osmia = crate::Osmia::default();
+	let err = osmia.run_code("{{capture 1 + 2}}{{done}}").unwrap_err().to_string();
+	assert!(err.contains("can only be stored in a variable"), "{}", err);
+	assert!(osmia.run_code("{{capture x}}a").is_err());
+	assert!(osmia.run_code("{{capture}}a{{done}}").is_err());
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::{
+	Osmia,
+	OsmiaError,
+	Syntax,
+};
+
+fn test_check(code: &str, expected_lines: Vec<usize>) {
+	let osmia = Osmia::default();
+	let diagnostics = osmia.check(code);
+	for diagnostic in &diagnostics {
+		println!("{}", diagnostic);
+	}
+	let lines: Vec<usize> = diagnostics.iter()
+		.map(|diagnostic| diagnostic.location().expect("The diagnostic should have a location").line())
+		.collect();
+	assert_eq!(lines, expected_lines);
+}
+
+macro_tests!(
+	test_check,
+	(valid01, "Hello {{ name }}!", vec![]),
+	(valid02, "{{for i in arr }}{{ i }}{{done}}", vec![]),
+	(valid03, "{{if a }}1{{elseif b }}2{{else}}3{{fi}}", vec![]),
+	(single_error01, "{{ 1 + }}", vec![1]),
+	(single_error02, "a\nb\n{{print }}", vec![3]),
+	(multiple_errors01, "{{ 1 + }}\n{{ 2 * }}\n{{ 3 - }}", vec![1, 2, 3]),
+	(multiple_errors02, "{{ 1 + }}\nok\n{{fn }}\n{{ 2 }}\n{{ ) }}", vec![1, 3, 5]),
+	(inside_block01, "{{for i in arr }}\n{{ 1 + }}\n{{ i }}\n{{done}}\n{{ 2 * }}", vec![2, 5]),
+	(inside_block02, "{{if a }}\n{{ 1 + }}\n{{else}}\n{{ 2 * }}\n{{fi}}", vec![2, 4]),
+	(inside_block03, "{{while true }}{{if a }}\n{{ 1 + }}\n{{fi}}{{done}}\n{{ ) }}", vec![2, 4]),
+	(invalid_header01, "{{for 1 in arr }}\n{{ i }}\n{{done}}\n{{ 1 + }}", vec![1, 4]),
+	(invalid_header02, "{{if }}\n{{ 1 + }}\n{{fi}}", vec![1, 2]),
+	(invalid_header03, "{{for i in arr }}{{if }}{{ i }}{{fi}}{{done}}\n{{ ) }}", vec![1, 2]),
+	(invalid_header04, "{{if a }}1{{elseif }}\n2{{else}}3{{fi}}\n{{ ) }}", vec![1, 3]),
+	(invalid_header05, "{{fn f; 1 }}{{return 1 }}{{done}}\n{{ f() }}\n{{ ) }}", vec![1, 3]),
+	(stray_closer01, "{{done}}\n{{fi}}\n{{ 1 }}", vec![1, 2]),
+	(stray_closer02, "{{for i in arr }}{{fi}}{{done}}\n{{ ) }}", vec![1, 2]),
+	(unclosed01, "{{ 1 + }}\n{{if true }}", vec![1, 2]),
+);
+
+#[test]
+fn check_first_error_matches_run_code() {
+	let code = "{{for i in arr }}\n{{ 1 + }}\n{{done}}\n{{ 2 * }}";
+	let mut osmia = Osmia::default();
+	let diagnostics = osmia.check(code);
+	assert_eq!(diagnostics.len(), 2);
+	let error = osmia.run_code(code).unwrap_err();
+	assert_eq!(diagnostics[0].error(), &error);
+	assert!(matches!(diagnostics[1].error(), OsmiaError::Parser(_)));
+	assert!(diagnostics[1].info().trace().is_some());
+}
+
+#[test]
+fn check_lexer_error() {
+	let osmia = Osmia::default();
+	let diagnostics = osmia.check("{{ 1 + }}\n{{ @ }}");
+	assert_eq!(diagnostics.len(), 1);
+	assert!(matches!(diagnostics[0].error(), OsmiaError::Lexer(_)));
+}
+
+#[test]
+fn check_does_not_run_the_code() {
+	let osmia = Osmia::default();
+	assert!(osmia.check("{{ undefined.value }}{{ 1 / 0 }}{{include \"missing.osmia\" }}").is_empty());
+}
+
+#[test]
+fn check_custom_syntax() {
+	let osmia = Osmia::default().with_syntax(Syntax::new("<%", "%>"));
+	assert!(osmia.check("{{ 1 + }}").is_empty());
+	assert_eq!(osmia.check("<% 1 + %>\n<% ) %>").len(), 2);
+}
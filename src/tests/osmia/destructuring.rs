@@ -0,0 +1,160 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		for_key_value,
+		Some("{{for k, v in obj}}{{ k }}={{ v }};{{done}}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::For,
+			Token::Whitespace,
+			Token::new_alpha("k"),
+			Token::Comma,
+			Token::Whitespace,
+			Token::new_alpha("v"),
+			Token::Whitespace,
+			Token::In,
+			Token::Whitespace,
+			Token::new_alpha("obj"),
+			Token::StmtEnd,
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("k"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::new_raw("="),
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("v"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::new_raw(";"),
+			Token::StmtStart,
+			Token::Done,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::For(For::new(
+			Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("v".into())),
+			Expr::Variable(Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("obj".into()))),
+			Stmt::Block(vec![
+				Expr::Variable(Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("k".into()))).into(),
+				Stmt::new_raw("="),
+				Expr::Variable(Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("v".into()))).into(),
+				Stmt::new_raw(";"),
+			].into()),
+		).with_key(Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("k".into()))))),
+		Some(vec![
+			(Ctx::try_from(r#"{"obj": {"a": 1, "b": 2}}"#).unwrap(), Ok("a=1;b=2;")),
+			(Ctx::try_from(r#"{"obj": [true, false]}"#).unwrap(), Ok("0=true;1=false;")),
+		])
+	),
+	(
+		for_object_pattern,
+		Some("{{for {name, age: years} in people}}{{done}}"),
+		None,
+		Some(Stmt::For(For::new(
+			Pattern::Object(vec![
+				("name".to_string(), Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("name".into())).into()),
+				("age".to_string(), Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("years".into())).into()),
+			]),
+			Expr::Variable(Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("people".into()))),
+			Stmt::Block(Block::new()),
+		))),
+		None
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		for_index_item,
+		"{{for i, item in arr}}{{ i }}:{{ item }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": ["a", "b", "c"]}"#).unwrap(), Ok("0:a 1:b 2:c "))]
+	),
+	(
+		for_object_without_key,
+		"{{for e in obj}}{{ e.key }}={{ e.value }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"obj": {"a": 1, "b": 2}}"#).unwrap(), Ok("a=1 b=2 "))]
+	),
+	(
+		for_object_destructuring,
+		"{{for {name, age} in people}}{{ name }} ({{ age }}) {{done}}",
+		None,
+		None,
+		vec![(
+			Ctx::try_from(r#"{"people": [{"name": "Arthur", "age": 42}, {"name": "Ford", "age": 200, "planet": "Betelgeuse"}]}"#).unwrap(),
+			Ok("Arthur (42) Ford (200) ")
+		)]
+	),
+	(
+		for_object_destructuring_rename,
+		"{{for {name: n, address: {city}} in people}}{{ n }}@{{ city }} {{done}}",
+		None,
+		None,
+		vec![(
+			Ctx::try_from(r#"{"people": [{"name": "Arthur", "address": {"city": "Cottington"}}]}"#).unwrap(),
+			Ok("Arthur@Cottington ")
+		)]
+	),
+	(
+		for_array_destructuring,
+		"{{for [x, y] in points}}({{ x }}, {{ y }}){{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"points": [[1, 2], [3, 4]]}"#).unwrap(), Ok("(1, 2)(3, 4)"))]
+	),
+	(
+		for_index_and_pattern,
+		"{{for i, {name} in people}}{{ i }}.{{ name }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"people": [{"name": "a"}, {"name": "b"}]}"#).unwrap(), Ok("0.a 1.b "))]
+	),
+	(
+		for_key_and_pattern,
+		"{{for id, {name} in users}}{{ id }}:{{ name }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"users": {"u1": {"name": "a"}, "u2": {"name": "b"}}}"#).unwrap(), Ok("u1:a u2:b "))]
+	),
+	(
+		for_destructuring_errors,
+		"{{for {name} in arr}}{{ name }}{{done}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"arr": [{"age": 1}]}"#).unwrap(), Err(vec!["destructure", "name"])),
+			(Ctx::try_from(r#"{"arr": [1]}"#).unwrap(), Err(vec!["destructure", "int"])),
+		]
+	),
+	(
+		for_array_destructuring_errors,
+		"{{for [a, b] in arr}}{{ a }}{{done}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"arr": [[1]]}"#).unwrap(), Err(vec!["1 items into 2 variables"])),
+			(Ctx::try_from(r#"{"arr": [{"a": 1}]}"#).unwrap(), Err(vec!["destructure", "object"])),
+		]
+	),
+);
+
+#[test]
+fn for_pattern_parse_errors() {
+	let mut osmia = crate::Osmia::default();
+	for code in [
+		"{{for k, in obj}}{{done}}",
+		"{{for {} in obj}}{{done}}",
+		"{{for {a b} in obj}}{{done}}",
+		"{{for [a, ] in obj}}{{done}}",
+		"{{for 1, v in obj}}{{done}}",
+	] {
+		assert!(osmia.run_code(code).is_err(), "{}", code);
+	}
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::{
+	Osmia,
+	OsmiaError,
+};
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+	Lexer,
+	Parser,
+	Runtime,
+	Ctx,
+	Conversion,
+	Limit,
+}
+
+fn test_error(
+	code: &str,
+	kind: Kind,
+	location: Option<(usize, usize)>,
+	culprit: Option<&str>,
+) {
+	let mut osmia = Osmia::default();
+	let err = osmia.run_code(code).unwrap_err();
+	println!("Error: {}", err);
+	let actual_kind = match &err {
+		OsmiaError::Lexer(_) => Kind::Lexer,
+		OsmiaError::Parser(_) => Kind::Parser,
+		OsmiaError::Runtime(_) => Kind::Runtime,
+		OsmiaError::Ctx(_) => Kind::Ctx,
+		OsmiaError::Conversion(_) => Kind::Conversion,
+		OsmiaError::Limit(_, _) => Kind::Limit,
+	};
+	assert_eq!(actual_kind, kind);
+	if let Some((line, column)) = location {
+		let actual = err.location().expect("The error should have a location");
+		assert_eq!((actual.line(), actual.column()), (line, column));
+	}
+	if let Some(culprit) = culprit {
+		assert_eq!(err.info().culprit(), Some(culprit));
+	}
+}
+
+macro_tests!(
+	test_error,
+	(lexer_error01, "{{ @ }}", Kind::Lexer, Some((1, 4)), Some("@")),
+	(lexer_error02, "Hello\n  world\n{{ 1 + # }}", Kind::Lexer, Some((3, 8)), Some("#")),
+	(lexer_error03, "{{ \"unclosed }}", Kind::Lexer, None, None),
+	(parser_error01, "{{ 1 + }}", Kind::Parser, Some((1, 8)), Some("}}")),
+	(parser_error02, "{{if true}}", Kind::Parser, Some((1, 12)), Some("Eof")),
+	(parser_error03, "a\n\tb {{ [1, 2 }}", Kind::Parser, Some((2, 13)), Some("}}")),
+	(runtime_error01, "{{ 1 / 0 }}", Kind::Runtime, Some((1, 6)), None),
+	(runtime_error02, "line 1\n{{ \"a\" - 1 }}", Kind::Runtime, Some((2, 8)), None),
+	(runtime_error03, "{{ \"a\"?foo() }}", Kind::Ctx, Some((1, 7)), None),
+	(runtime_error04, "{{ 1 }}\n{{ x = 1 }}{{ x() }}", Kind::Runtime, Some((2, 15)), None),
+	(runtime_error05, "{{fn f}}\n\t{{ 1 / 0 }}\n{{done}}\n{{ f() }}", Kind::Runtime, Some((2, 7)), None),
+	(runtime_error06, "{{ x = 1 }}\n{{for i in x}}{{done}}", Kind::Runtime, Some((2, 3)), None),
+	(ctx_error01, "{{ user }}", Kind::Ctx, Some((1, 4)), Some("user")),
+	(ctx_error02, "\n\n  {{ user.name }}", Kind::Ctx, Some((3, 6)), Some("user.name")),
+	(ctx_error03, "{{ arr = [1] }}{{ arr[3] }}", Kind::Ctx, Some((1, 19)), Some("arr[3]")),
+);
+
+#[test]
+fn error_display() {
+	let mut osmia = Osmia::default();
+	let err = osmia.run_code("{{ 1 + }}").unwrap_err().to_string();
+	assert!(err.starts_with("Parser error: "));
+	let err = osmia.run_code("Hello\n{{ @ }}").unwrap_err().to_string();
+	assert!(err.starts_with("Lexer error: Line 2: "));
+	let err = osmia.run_code("{{ user.name }}").unwrap_err().to_string();
+	assert!(err.starts_with("Line 1: user not found\n"));
+	assert!(err.contains("{{ user.name }}"));
+}
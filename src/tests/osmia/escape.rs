@@ -0,0 +1,123 @@
+use crate::{
+	Osmia,
+	EscapeMode,
+	MapLoader,
+};
+
+const CTX: &str = r#"{
+	"name": "<b>\"Tom\" & 'Jerry'</b>",
+	"cmd": "rm -rf / ; echo 'done'",
+	"items": ["<a>", "b"]
+}"#;
+
+fn run(escape: EscapeMode, code: &str) -> String {
+	Osmia::try_from(CTX).unwrap().with_escape(escape).run_code(code).unwrap()
+}
+
+#[test]
+fn escape_none_by_default() {
+	let mut osmia = Osmia::try_from(CTX).unwrap();
+	assert_eq!(osmia.run_code("{{ name }}").unwrap(), "<b>\"Tom\" & 'Jerry'</b>");
+}
+
+#[test]
+fn escape_html() {
+	assert_eq!(
+		run(EscapeMode::Html, "<p>{{ name }}</p>"),
+		"<p>&lt;b&gt;&quot;Tom&quot; &amp; &#x27;Jerry&#x27;&lt;/b&gt;</p>"
+	);
+	assert_eq!(run(EscapeMode::Html, "{{ items }}"), "[&quot;&lt;a&gt;&quot;, &quot;b&quot;]");
+	assert_eq!(run(EscapeMode::Html, "{{ 1 < 2 }}"), "true");
+}
+
+#[test]
+fn escape_xml() {
+	assert_eq!(
+		run(EscapeMode::Xml, "<name>{{ name }}</name>"),
+		"<name>&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;</name>"
+	);
+}
+
+#[test]
+fn escape_json_string() {
+	assert_eq!(
+		run(EscapeMode::JsonString, "{\"name\": \"{{ name }}\"}"),
+		r#"{"name": "<b>\"Tom\" & 'Jerry'</b>"}"#
+	);
+	let mut osmia = Osmia::try_from(r#"{"text": "a\nb\\c"}"#).unwrap()
+		.with_escape(EscapeMode::JsonString);
+	assert_eq!(osmia.run_code("\"{{ text }}\"").unwrap(), r#""a\nb\\c""#);
+}
+
+#[test]
+fn escape_shell() {
+	assert_eq!(run(EscapeMode::Shell, "echo {{ cmd }}"), r#"echo 'rm -rf / ; echo '\''done'\'''"#);
+	assert_eq!(run(EscapeMode::Shell, "echo {{ \"\" }}"), "echo ''");
+}
+
+#[test]
+fn escape_custom() {
+	let escape = EscapeMode::custom(|s| s.replace('&', "and"));
+	assert_eq!(run(escape, "{{ name }}"), "<b>\"Tom\" and 'Jerry'</b>");
+}
+
+#[test]
+fn safe_values() {
+	assert_eq!(run(EscapeMode::Html, "{{ name?safe() }}"), "<b>\"Tom\" & 'Jerry'</b>");
+	assert_eq!(run(EscapeMode::Html, "{{ (name?upper())?safe() }}"), "<B>\"TOM\" & 'JERRY'</B>");
+	assert_eq!(run(EscapeMode::Html, "{{ (items?safe()) }}"), r#"["<a>", "b"]"#);
+	assert_eq!(run(EscapeMode::Html, "{{ name?safe()?len() }}"), "22");
+	assert_eq!(run(EscapeMode::Html, "{{ x = name?safe() }}{{ x }}"), run(EscapeMode::Html, "{{ name }}"));
+}
+
+#[test]
+fn raw_text_is_not_escaped() {
+	assert_eq!(run(EscapeMode::Html, "<a href='x'>&amp;</a>"), "<a href='x'>&amp;</a>");
+}
+
+#[test]
+fn loops_and_conditionals() {
+	assert_eq!(
+		run(EscapeMode::Html, "{{for i in items}}<li>{{ i }}</li>{{done}}"),
+		"<li>&lt;a&gt;</li><li>b</li>"
+	);
+	assert_eq!(run(EscapeMode::Html, "{{if true}}<i>{{ items[0] }}</i>{{fi}}"), "<i>&lt;a&gt;</i>");
+}
+
+#[test]
+fn functions() {
+	let code = "{{fn card; title}}<div>{{ title }}</div>{{done}}";
+	assert_eq!(
+		run(EscapeMode::Html, &format!("{}{{{{ card(items[0]) }}}}", code)),
+		"<div>&lt;a&gt;</div>"
+	);
+	assert_eq!(
+		run(EscapeMode::Html, "{{fn f; x}}{{return x}}{{done}}{{ f(items[0]) }}"),
+		"&lt;a&gt;"
+	);
+	assert_eq!(run(EscapeMode::Html, "{{ (fn (x) => x)(items[0]) }}"), "&lt;a&gt;");
+	assert_eq!(run(EscapeMode::Html, "{{ items?map(fn (x) => x)[0] }}"), "&lt;a&gt;");
+}
+
+#[test]
+fn includes() {
+	let mut osmia = Osmia::try_from(CTX).unwrap()
+		.with_escape(EscapeMode::Html)
+		.with_loader(MapLoader::new().add("item", "<li>{{ item }}</li>"));
+	assert_eq!(
+		osmia.run_code("{{for item in items}}{{include \"item\"}}{{done}}").unwrap(),
+		"<li>&lt;a&gt;</li><li>b</li>"
+	);
+}
+
+#[test]
+fn escape_per_render() {
+	let osmia = Osmia::try_from(CTX).unwrap();
+	let template = osmia.compile("{{ items[0] }}").unwrap();
+	assert_eq!(template.render_with(CTX).unwrap(), "<a>");
+	let template = template.with_escape(EscapeMode::Xml);
+	assert_eq!(template.render_with(CTX).unwrap(), "&lt;a&gt;");
+	let mut output = Vec::new();
+	template.render_to(&mut crate::Ctx::try_from(CTX).unwrap(), &mut output).unwrap();
+	assert_eq!(output, b"&lt;a&gt;");
+}
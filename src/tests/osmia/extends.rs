@@ -0,0 +1,156 @@
+use super::*;
+use crate::Osmia;
+
+const BASE: &str = "<title>{{block title}}Osmia{{done}}</title>
+{{block body}}
+<p>Default body</p>
+{{done}}
+<footer>{{block footer}}(c) {{ year }}{{done}}</footer>";
+
+fn test_extends(
+	mut templates: Vec<(&str, &str)>,
+	code: &str,
+	expected: Result<&str, Vec<&str>>,
+) {
+	templates.insert(0, ("base", BASE));
+	test_with_loader(r#"{ "year": 2024, "items": [1, 2] }"#, templates, code, expected);
+}
+
+macro_tests!(
+	test,
+	(
+		extends_parse01,
+		Some("{{extends \"base\"}}\n{{block title}}Home {{parent}}{{done}}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Extends,
+			Token::Whitespace,
+			Token::new_str("base"),
+			Token::StmtEnd,
+			Token::NewLineNonPrintable,
+			Token::StmtStart,
+			Token::Block,
+			Token::Whitespace,
+			Token::new_alpha("title"),
+			Token::StmtEnd,
+			Token::new_raw("Home "),
+			Token::StmtStart,
+			Token::Parent,
+			Token::StmtEnd,
+			Token::StmtStart,
+			Token::Done,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::Block(vec![
+			Stmt::Extends("base".into()),
+			Stmt::NewLineNonPrintable,
+			Stmt::NamedBlock(NamedBlock::new("title", Stmt::Block(vec![
+				Stmt::new_raw("Home "),
+				Stmt::Parent,
+			].into()))),
+		].into())),
+		None
+	),
+);
+
+macro_tests!(
+	test_extends,
+	(
+		extends01,
+		vec![],
+		"{{extends \"base\"}}",
+		Ok("<title>Osmia</title>\n<p>Default body</p>\n<footer>(c) 2024</footer>")
+	),
+	(
+		extends02,
+		vec![],
+		"{{extends \"base\"}}\n{{block title}}Home{{done}}\n",
+		Ok("<title>Home</title>\n<p>Default body</p>\n<footer>(c) 2024</footer>")
+	),
+	(
+		extends03,
+		vec![],
+		"{{extends \"base\"}}\n{{block title}}Home - {{parent}}{{done}}\n{{block body}}\n{{for i in items}}\n<p>{{ i }}</p>\n{{done}}\n{{done}}\n",
+		Ok("<title>Home - Osmia</title>\n<p>1</p>\n<p>2</p>\n<footer>(c) 2024</footer>")
+	),
+	(
+		extends04,
+		vec![],
+		"Ignored content\n{{extends \"base\"}}\n{{block footer}}{{ year + 1 }}{{done}}\n",
+		Ok("<title>Osmia</title>\n<p>Default body</p>\n<footer>2025</footer>")
+	),
+	(
+		extends05,
+		vec![("page", "{{extends \"base\"}}{{block title}}Page{{done}}{{block body}}<main>{{block content}}{{done}}</main>\n{{done}}")],
+		"{{extends \"page\"}}{{block title}}{{parent}} 2{{done}}{{block content}}Hi{{done}}",
+		Ok("<title>Page 2</title>\n<main>Hi</main>\n<footer>(c) 2024</footer>")
+	),
+	(
+		extends06,
+		vec![("child", "{{extends \"base\"}}{{block title}}Child{{done}}")],
+		"[{{include \"child\"}}]",
+		Ok("[<title>Child</title>\n<p>Default body</p>\n<footer>(c) 2024</footer>]")
+	),
+	(
+		extends07,
+		vec![],
+		"{{block title}}Standalone{{done}}",
+		Ok("Standalone")
+	),
+	(
+		extends_keywords_as_variables,
+		vec![],
+		"{{ parent = {\"name\": \"root\"} }}{{ parent.name }} {{parent.name}} {{ block = 1 }}{{ block }} {{ extends = 2 }}{{ extends }} {{for parent in items}}{{ parent }}{{done}}",
+		Ok("root root 1 2 12")
+	),
+	(
+		extends_error01,
+		vec![],
+		"{{extends \"missing\"}}",
+		Err(vec!["not found", "missing"])
+	),
+	(
+		extends_error02,
+		vec![("a", "{{extends \"b\"}}"), ("b", "{{extends \"a\"}}")],
+		"{{extends \"a\"}}",
+		Err(vec!["cycle", "a -> b -> a"])
+	),
+	(
+		extends_error03,
+		vec![],
+		"{{extends \"base\"}}{{block title}}a{{done}}{{block title}}b{{done}}",
+		Err(vec!["title", "more than once"])
+	),
+	(
+		extends_error04,
+		vec![],
+		"{{extends \"base\"}}{{extends \"base\"}}",
+		Err(vec!["only extend one"])
+	),
+	(
+		extends_error05,
+		vec![],
+		"{{if true}}{{extends \"base\"}}{{fi}}",
+		Err(vec!["top level"])
+	),
+	(
+		extends_error06,
+		vec![],
+		"{{block title}}{{parent}}{{done}}",
+		Err(vec!["parent", "overrides"])
+	),
+	(
+		extends_error07,
+		vec![],
+		"{{extends base}}",
+		Err(vec!["parser error", "string"])
+	),
+);
+
+#[test]
+fn extends_without_loader() {
+	let mut osmia = Osmia::default();
+	let err = osmia.run_code("{{extends \"base\"}}").unwrap_err();
+	assert!(err.to_string().contains("template loader"));
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::{
+	Osmia,
+	MAIN_OUTPUT,
+	MapLoader,
+};
+
+fn render_files(ctx: &str, code: &str) -> Result<BTreeMap<String, String>, String> {
+	Osmia::try_from(ctx).unwrap().render_files(code).map_err(|e| e.to_string())
+}
+
+fn files(files: &[(&str, &str)]) -> BTreeMap<String, String> {
+	files.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+macro_tests!(
+	test,
+	(
+		file_block_01,
+		Some("{{file \"a\"}}b{{done}}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::FileBlock,
+			Token::Whitespace,
+			Token::new_str("a"),
+			Token::StmtEnd,
+			Token::new_raw("b"),
+			Token::StmtStart,
+			Token::Done,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::FileBlock(FileBlock::new(
+			Expr::Str("a".into()),
+			Stmt::new_raw("b"),
+		))),
+		Some(vec![(Ctx::new(), Ok(""))])
+	),
+);
+
+#[test]
+fn file_block_tree() {
+	let ctx = r#"{ "models": [{ "name": "user", "fields": ["id", "name"] }, { "name": "post", "fields": ["id"] }] }"#;
+	let code = "// generated
+{{for m in models}}
+{{file \"src/models/\" + m.name + \".rs\"}}
+pub struct {{ m.name?capitalize() }} {
+{{for f in m.fields}}
+	{{ f }}: String,
+{{done}}
+}
+{{done}}
+pub mod {{ m.name }};
+{{done}}";
+	assert_eq!(render_files(ctx, code), Ok(files(&[
+		(MAIN_OUTPUT, "// generated\npub mod user;\npub mod post;\n"),
+		("src/models/post.rs", "pub struct Post {\n\tid: String,\n}\n"),
+		("src/models/user.rs", "pub struct User {\n\tid: String,\n\tname: String,\n}\n"),
+	])));
+}
+
+#[test]
+fn file_block_append() {
+	assert_eq!(
+		render_files("{}", "{{for i in [1, 2, 3]}}{{file \"log\"}}{{ i }};{{done}}{{done}}main"),
+		Ok(files(&[(MAIN_OUTPUT, "main"), ("log", "1;2;3;")]))
+	);
+}
+
+#[test]
+fn file_block_nested() {
+	assert_eq!(
+		render_files("{}", "{{file \"a\"}}a{{file \"b\"}}b{{done}}a{{done}}"),
+		Ok(files(&[(MAIN_OUTPUT, ""), ("a", "aa"), ("b", "b")]))
+	);
+}
+
+#[test]
+fn file_block_control_flow() {
+	assert_eq!(
+		render_files("{}", "{{for i in [1, 2, 3]}}{{file \"f\"}}{{ i }}{{if i == 2}}{{break}}{{fi}}{{done}}{{done}}"),
+		Ok(files(&[(MAIN_OUTPUT, ""), ("f", "12")]))
+	);
+	assert_eq!(
+		render_files("{}", "{{fn f}}{{file \"f\"}}a{{return 1}}{{done}}{{done}}{{ f() }}"),
+		Ok(files(&[(MAIN_OUTPUT, "1"), ("f", "a")]))
+	);
+}
+
+#[test]
+fn file_block_include() {
+	let mut osmia = Osmia::default()
+		.with_loader(MapLoader::new().add("readme", "{{file \"README.md\"}}# {{ name }}{{done}}"));
+	osmia.set("name", "osmia").unwrap();
+	assert_eq!(
+		osmia.render_files("{{include \"readme\"}}ok").unwrap(),
+		files(&[(MAIN_OUTPUT, "ok"), ("README.md", "# osmia")])
+	);
+}
+
+#[test]
+fn file_block_without_files() {
+	assert_eq!(render_files("{}", "a{{ 1 }}"), Ok(files(&[(MAIN_OUTPUT, "a1")])));
+	let mut osmia = Osmia::default();
+	assert_eq!(osmia.run_code("a{{file \"f\"}}b{{done}}c").unwrap(), "ac");
+}
+
+#[test]
+fn file_block_errors() {
+	let err = render_files("{}", "{{file 1}}a{{done}}").unwrap_err();
+	assert!(err.contains("The path of a file must be a string, got 1"), "{}", err);
+	let err = render_files("{}", "{{file \"\"}}a{{done}}").unwrap_err();
+	assert!(err.contains("The path of a file cannot be empty"), "{}", err);
+	assert!(render_files("{}", "{{file \"a\"}}a").is_err());
+}
+
+#[test]
+fn file_block_file_as_variable() {
+	let mut osmia = Osmia::try_from(r#"{"files": ["a", "b"], "file": {"name": "c"}}"#).unwrap();
+	assert_eq!(osmia.run_code("{{ file.name }} {{file.name}} {{ file }}").unwrap(), "c c {\"name\": \"c\"}");
+	assert_eq!(osmia.run_code("{{for file in files}}{{file}}{{done}}").unwrap(), "ab");
+	assert_eq!(osmia.run_code("{{file = 1}}{{ file }}").unwrap(), "1");
+}
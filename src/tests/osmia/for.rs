@@ -452,3 +452,88 @@ macro_tests!(
 		None
 	)
 );
+
+macro_tests!(
+	test_code,
+	(
+		foreach_method_call,
+		"{{for x in items?reverse()}}{{x}}{{done}} {{for x in items?filter(fn (i) => i > 1)}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"items": [1, 2, 3]}"#).unwrap(), Ok("321 23"))]
+	),
+	(
+		foreach_function_call,
+		"{{fn get_items}}{{return [1, 2]}}{{done}}{{for x in get_items()}}{{x}}{{done}} {{for k in obj?keys()?sort()}}{{k}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"obj": {"b": 1, "a": 2}}"#).unwrap(), Ok("12 ab"))]
+	),
+	(
+		foreach_grouping,
+		"{{for x in (a + b)}}{{x}}{{done}} {{for x in a + [9]}}{{x}}{{done}} {{for x in a if c else b}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"a": [1, 2], "b": [3], "c": false}"#).unwrap(), Ok("123 129 3"))]
+	),
+	(
+		foreach_string,
+		"{{for c in \"abc\"}}{{c}}-{{done}} {{for i, c in name}}{{i}}{{c}}{{done}} {{for c in \"\"}}x{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"name": "añb"}"#).unwrap(), Ok("a-b-c- 0a1ñ2b "))]
+	),
+	(
+		foreach_string_loop_variable,
+		"{{for c in \"añb\"}}{{c}}{{if !loop.last}},{{fi}}{{done}} {{for c in \"xy\"}}{{loop.length}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("a,ñ,b 22"))]
+	),
+	(
+		foreach_range_variable,
+		"{{for i in (1..=n)?reverse()}}{{i}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"n": 3}"#).unwrap(), Ok("321"))]
+	),
+	(
+		foreach_not_iterable_int,
+		"{{for x in 5}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["cannot iterate over a value of type int: 5"]))]
+	),
+	(
+		foreach_not_iterable_variable,
+		"{{for x in n}}{{x}}{{done}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"n": null}"#).unwrap(), Err(vec!["cannot iterate over a value of type null: null"])),
+			(Ctx::try_from(r#"{"n": true}"#).unwrap(), Err(vec!["cannot iterate over a value of type bool"])),
+			(Ctx::try_from(r#"{"n": 1.5}"#).unwrap(), Err(vec!["cannot iterate over a value of type float"])),
+		]
+	),
+	(
+		foreach_not_iterable_call,
+		"{{for x in items?len()}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"items": [1, 2]}"#).unwrap(), Err(vec!["cannot iterate over a value of type int: 2"]))]
+	),
+	(
+		foreach_not_iterable_function,
+		"{{for x in math.max}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["cannot iterate over a value of type function"]))]
+	),
+	(
+		foreach_missing_variable,
+		"{{for x in missing}}{{x}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["missing"]))]
+	),
+);
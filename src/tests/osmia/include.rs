@@ -0,0 +1,179 @@
+use super::*;
+use crate::{
+	Osmia,
+	FsLoader,
+};
+
+fn test_include(
+	templates: Vec<(&str, &str)>,
+	code: &str,
+	expected: Result<&str, Vec<&str>>,
+) {
+	test_with_loader(r#"{ "user": "Marvin", "names": ["a", "b"] }"#, templates, code, expected);
+}
+
+macro_tests!(
+	test,
+	(
+		include_parse01,
+		Some(r#"{{include "header"}}"#),
+		Some(vec![
+			Token::StmtStart,
+			Token::Include,
+			Token::Whitespace,
+			Token::new_str("header"),
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::Include(Include::new(Expr::new_str("header")))),
+		None
+	),
+	(
+		include_parse02,
+		Some(r#"{{include "parts/" + name }}"#),
+		None,
+		Some(Stmt::Include(Include::new(Binary::new(
+			Expr::new_str("parts/"),
+			BinaryOp::Plus,
+			Variable::from_vec(vec![
+				JsonTreeKeyExpr::JsonTreeKey("name".into())
+			]).into()
+		).into()))),
+		None
+	),
+);
+
+macro_tests!(
+	test_include,
+	(
+		include01,
+		vec![("header", "<h1>{{ user }}</h1>")],
+		r#"{{include "header"}}"#,
+		Ok("<h1>Marvin</h1>")
+	),
+	(
+		include02,
+		vec![("header", "<h1>"), ("footer", "</h1>")],
+		"{{include \"header\"}}{{ user }}{{include \"footer\"}}",
+		Ok("<h1>Marvin</h1>")
+	),
+	(
+		include03,
+		vec![("item", "- {{ name }}")],
+		"{{for name in names}}\n{{include \"item\"}}\n{{done}}",
+		Ok("- a\n- b\n")
+	),
+	(
+		include04,
+		vec![("set", "{{ user = \"R2D2\" }}")],
+		"{{include \"set\"}}{{ user }}",
+		Ok("R2D2")
+	),
+	(
+		include05,
+		vec![("a", "a{{include \"b\"}}"), ("b", "b{{include \"c\"}}"), ("c", "c")],
+		"{{include \"a\"}}",
+		Ok("abc")
+	),
+	(
+		include06,
+		vec![("part_1", "one")],
+		"{{ i = 1 }}{{include \"part_\" + i?to_string() }}",
+		Ok("one")
+	),
+	(
+		include_error01,
+		vec![],
+		"{{include \"header\"}}",
+		Err(vec!["not found", "header"])
+	),
+	(
+		include_error02,
+		vec![("a", "{{include \"b\"}}"), ("b", "{{include \"a\"}}")],
+		"{{include \"a\"}}",
+		Err(vec!["cycle", "a -> b -> a"])
+	),
+	(
+		include_error03,
+		vec![("a", "{{include \"a\"}}")],
+		"{{include \"a\"}}",
+		Err(vec!["cycle", "a -> a"])
+	),
+	(
+		include_error04,
+		vec![],
+		"{{include 1}}",
+		Err(vec!["must be a string"])
+	),
+	(
+		include_error05,
+		vec![("a", "\n{{ 1 + }}")],
+		"{{include \"a\"}}",
+		Err(vec!["parser error", "line 2"])
+	),
+	(
+		include_error06,
+		vec![("a", "{{ missing }}")],
+		"text\n{{include \"a\"}}",
+		Err(vec!["missing not found", "line 1"])
+	),
+	(
+		include_error07,
+		vec![("a", "{{break}}")],
+		"{{for n in names}}{{include \"a\"}}{{done}}",
+		Err(vec!["included template"])
+	),
+	(
+		include_as_variable_name,
+		vec![("a", "A")],
+		"{{ include = \"a\" }}{{include include}} {{ include }} {{for include in names}}{{ include }}{{done}}",
+		Ok("A a ab")
+	),
+);
+
+#[test]
+fn include_without_loader() {
+	let mut osmia = Osmia::default();
+	let err = osmia.run_code("{{include \"header\"}}").unwrap_err();
+	assert!(err.to_string().contains("template loader"));
+}
+
+#[test]
+fn include_fs_loader() {
+	let root = std::env::temp_dir().join(format!("osmia_include_{}", std::process::id()));
+	std::fs::create_dir_all(root.join("parts")).unwrap();
+	std::fs::write(root.join("parts/header.txt"), "Hello {{ user }}").unwrap();
+	let mut osmia = Osmia::try_from(r#"{ "user": "Marvin" }"#)
+		.unwrap()
+		.with_loader(FsLoader::new(&root));
+	let output = osmia.run_code("{{include \"parts/header.txt\"}}!");
+	let not_found = osmia.run_code("{{include \"parts/footer.txt\"}}");
+	let outside = osmia.run_code("{{include \"../header.txt\"}}");
+	std::fs::remove_dir_all(&root).unwrap();
+	assert_eq!(output.unwrap(), "Hello Marvin!");
+	assert!(not_found.unwrap_err().to_string().contains("not found"));
+	assert!(outside.unwrap_err().to_string().contains("Invalid template name"));
+}
+
+#[test]
+fn include_loaded_once_per_template() {
+	use std::rc::Rc;
+	use std::cell::Cell;
+
+	struct CountingLoader(Rc<Cell<usize>>);
+
+	impl crate::TemplateLoader for CountingLoader {
+		fn load(&self, name: &str) -> Result<String, crate::OsmiaError> {
+			self.0.set(self.0.get() + 1);
+			Ok(format!("<{}:{{{{ i }}}}>", name))
+		}
+	}
+
+	let loads = Rc::new(Cell::new(0));
+	let osmia = Osmia::default().with_loader(CountingLoader(loads.clone()));
+	let template = osmia.compile("{{for i in [1, 2]}}{{include \"a\"}}{{include \"b\"}}{{done}}").unwrap();
+	assert_eq!(loads.get(), 0);
+	assert_eq!(template.render_with("{}").unwrap(), "<a:1><b:1><a:2><b:2>");
+	assert_eq!(template.render_with("{}").unwrap(), "<a:1><b:1><a:2><b:2>");
+	assert_eq!(loads.get(), 2);
+}
@@ -225,3 +225,16 @@ macro_tests!(
 		None
 	)
 );
+
+macro_tests!(
+	test,
+	(
+		lambda_spread_expressions,
+		Some("{{ f = fn (foo, ...ops) => ops }}{{ f(1, 1 + 1, \"a\" + \"b\") }}"),
+		None,
+		None,
+		Some(vec![
+			(Ctx::new(), Ok(r#"[2, "ab"]"#)),
+		])
+	)
+);
@@ -0,0 +1,102 @@
+use crate::{
+	Osmia,
+	OsmiaError,
+	Limits,
+	LimitKind,
+};
+
+const RECURSION: &str = "{{fn f; n}}{{return f(n + 1)}}{{done}}{{ f(0) }}";
+
+fn limit_kind(limits: Limits, code: &str) -> Option<LimitKind> {
+	match Osmia::default().with_limits(limits).run_code(code) {
+		Err(OsmiaError::Limit(kind, _)) => Some(kind),
+		Err(e) => panic!("Unexpected error: {}", e),
+		Ok(_) => None,
+	}
+}
+
+#[test]
+fn no_limits_by_default() {
+	let mut osmia = Osmia::default();
+	assert_eq!(
+		osmia.run_code("{{ i = 0 }}{{while i < 1000}}{{ i = i + 1 }}.{{done}}").unwrap().len(),
+		1000
+	);
+}
+
+#[test]
+fn fuel() {
+	let limits = Limits::new().with_fuel(1000);
+	assert_eq!(limit_kind(limits, "{{while true}}{{done}}"), Some(LimitKind::Fuel));
+	assert_eq!(limit_kind(limits, "{{for i in [1, 2, 3]}}{{ i }}{{done}}"), None);
+	assert_eq!(
+		limit_kind(limits, "{{ i = 0 }}{{while i < 1000}}{{ i = i + 1 }}{{done}}"),
+		Some(LimitKind::Fuel)
+	);
+}
+
+#[test]
+fn call_depth() {
+	let limits = Limits::new().with_call_depth(20);
+	assert_eq!(limit_kind(limits, RECURSION), Some(LimitKind::CallDepth));
+	assert_eq!(limit_kind(limits, "{{ f = fn (n) => f(n) }}{{ f(1) }}"), Some(LimitKind::CallDepth));
+	assert_eq!(
+		limit_kind(limits, "{{fn f; n}}{{if n > 0}}{{return f(n - 1)}}{{fi}}{{return n}}{{done}}{{ f(19) }}"),
+		None
+	);
+	assert_eq!(
+		limit_kind(limits, "{{fn f; n}}{{if n > 0}}{{return f(n - 1)}}{{fi}}{{return n}}{{done}}{{ f(20) }}"),
+		Some(LimitKind::CallDepth)
+	);
+}
+
+#[test]
+fn call_depth_resets_between_calls() {
+	let limits = Limits::new().with_call_depth(2);
+	assert_eq!(
+		limit_kind(limits, "{{for i in [1, 2, 3, 4, 5]}}{{ math.abs(math.abs(i)) }}{{done}}"),
+		None
+	);
+	assert_eq!(limit_kind(limits, "{{ math.abs(math.abs(math.abs(1))) }}"), Some(LimitKind::CallDepth));
+}
+
+#[test]
+fn output() {
+	let limits = Limits::new().with_output(10);
+	assert_eq!(limit_kind(limits, "0123456789"), None);
+	assert_eq!(limit_kind(limits, "0123456789a"), Some(LimitKind::Output));
+	assert_eq!(limit_kind(limits, "{{while true}}a{{done}}"), Some(LimitKind::Output));
+	assert_eq!(limit_kind(limits, "{{for i in [1, 2, 3, 4, 5, 6]}}{{ i }}.{{done}}"), Some(LimitKind::Output));
+	assert_eq!(limit_kind(limits, "{{ \"0123456789\" + \"a\" }}"), Some(LimitKind::Output));
+}
+
+#[test]
+fn collection_size() {
+	let limits = Limits::new().with_collection_size(3);
+	assert_eq!(limit_kind(limits, "{{ [1, 2, 3] }}"), None);
+	assert_eq!(limit_kind(limits, "{{ [1, 2, 3, 4] }}"), Some(LimitKind::CollectionSize));
+	assert_eq!(limit_kind(limits, "{{ {\"a\": 1, \"b\": 2, \"c\": 3, \"d\": 4} }}"), Some(LimitKind::CollectionSize));
+	assert_eq!(limit_kind(limits, "{{ [1, 2] + [3, 4] }}"), Some(LimitKind::CollectionSize));
+	assert_eq!(
+		limit_kind(limits, "{{ a = [1] }}{{while true}}{{ a = a + a }}{{done}}"),
+		Some(LimitKind::CollectionSize)
+	);
+	assert_eq!(limit_kind(limits, "{{ \"a,b,c,d\"?split(\",\") }}"), Some(LimitKind::CollectionSize));
+}
+
+#[test]
+fn limit_error() {
+	let mut osmia = Osmia::default().with_limits(Limits::new().with_fuel(100));
+	let err = osmia.run_code("Hi\n{{ f = fn (n) => f(n) }}{{ f(1) }}").unwrap_err();
+	assert_eq!(err.location().unwrap().line(), 2);
+	assert!(err.to_string().starts_with("Limit error: Line 2: Fuel exhausted"));
+}
+
+#[test]
+fn template_limits() {
+	let osmia = Osmia::default().with_limits(Limits::new().with_fuel(100));
+	let template = osmia.compile("{{while true}}ab{{done}}").unwrap();
+	assert!(matches!(template.render_with("{}"), Err(OsmiaError::Limit(LimitKind::Fuel, _))));
+	let template = template.with_limits(Limits::new().with_output(1000));
+	assert!(matches!(template.render_with("{}"), Err(OsmiaError::Limit(LimitKind::Output, _))));
+}
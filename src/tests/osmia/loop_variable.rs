@@ -0,0 +1,131 @@
+use super::*;
+
+macro_tests!(
+	test_code,
+	(
+		loop_index,
+		"{{for x in arr}}{{ loop.index }}{{ loop.index0 }}{{ loop.revindex }}{{ loop.revindex0 }}{{ loop.length }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": ["a", "b", "c"]}"#).unwrap(), Ok("10323 21213 32103 "))]
+	),
+	(
+		loop_first_last,
+		"{{for x in arr}}{{if loop.first}}[{{fi}}{{ x }}{{if loop.last}}]{{else}}, {{fi}}{{done}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"arr": [1, 2, 3]}"#).unwrap(), Ok("[1, 2, 3]")),
+			(Ctx::try_from(r#"{"arr": [1]}"#).unwrap(), Ok("[1]")),
+			(Ctx::try_from(r#"{"arr": []}"#).unwrap(), Ok("")),
+		]
+	),
+	(
+		loop_zebra,
+		"{{for x in arr}}{{ \"odd\" if loop.index % 2 else \"even\" }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": [1, 2, 3]}"#).unwrap(), Ok("odd even odd "))]
+	),
+	(
+		loop_object,
+		"{{for k, v in obj}}{{ k }}{{ loop.index }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"obj": {"a": 1, "b": 2}}"#).unwrap(), Ok("a1b2"))]
+	),
+	(
+		loop_outer,
+		"{{for row in matrix}}{{for cell in row}}{{ loop.outer.index }}.{{ loop.index }} {{done}}{{ loop.outer }}|{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"matrix": [[1, 2], [3]]}"#).unwrap(), Ok("1.1 1.2 null|2.1 null|"))]
+	),
+	(
+		loop_restored_after_nested,
+		"{{for a in [1, 2]}}{{for b in [1, 2, 3]}}{{done}}{{ loop.length }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("22"))]
+	),
+	(
+		loop_break_and_continue,
+		"{{for x in [1, 2, 3, 4]}}{{if loop.first}}{{continue}}{{fi}}{{ loop.index }}{{if loop.revindex == 2}}{{break}}{{fi}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("23"))]
+	),
+	(
+		loop_as_value,
+		"{{for x in [1]}}{{ l = loop }}{{done}}{{ l.first }} {{ l.outer }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("true null"))]
+	),
+	(
+		loop_outside_for,
+		"{{ loop }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"loop": "ctx value"}"#).unwrap(), Ok("ctx value")),
+			(Ctx::new(), Err(vec!["loop", "not found"])),
+		]
+	),
+	(
+		loop_ctx_variable,
+		"{{for x in [1]}}{{ loop }}{{done}} {{ loop }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"loop": "ctx value"}"#).unwrap(), Ok("ctx value ctx value"))]
+	),
+	(
+		loop_shadowed_by_pattern,
+		"{{for loop in files}}{{ loop }}{{done}} {{for loop, f in files}}{{ loop }}{{ f }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"files": ["a", "b"]}"#).unwrap(), Ok("ab 0a1b"))]
+	),
+	(
+		loop_shadowed_by_assign,
+		"{{for x in [1, 2]}}{{ loop.index }}{{ loop = {\"index\": 7} }}{{ loop.index }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1777"))]
+	),
+	(
+		loop_hidden_in_functions,
+		"{{fn h}}{{ loop.index ?? \"-\" }}{{done}}{{for x in [1, 2]}}{{ h() }}{{ loop.index }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("-1-2"))]
+	),
+	(
+		loop_in_function_loop,
+		"{{fn h}}{{for y in [1, 2, 3]}}{{ loop.index }}{{ loop.outer }}{{done}}{{done}}{{for x in [1]}}{{ h() }} {{ loop.length }}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1null2null3null 1"))]
+	),
+	(
+		loop_length_boundary,
+		"{{for i in 0..9223372036854775807}}{{ loop.length }} {{ loop.revindex }}{{break}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("9223372036854775807 9223372036854775807"))]
+	),
+	(
+		loop_length_overflow,
+		"{{for i in 0..=9223372036854775807}}{{ loop.length }}{{break}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["position 9223372036854775808 of the loop does not fit in an int"]))]
+	),
+	(
+		loop_in_while,
+		"{{for x in [1, 2]}}{{ i = 0 }}{{while i < 2}}{{ i = i + 1 }}{{ loop.index }}{{done}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1122"))]
+	),
+);
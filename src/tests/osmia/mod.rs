@@ -1,17 +1,38 @@
 mod assign;
 mod basic;
 mod callable;
+mod capture;
+mod check;
 mod comment;
+mod destructuring;
+mod error;
+mod escape;
+mod extends;
+mod file_block;
 mod r#for;
 mod function;
 mod get_ctx;
 mod gh;
 mod r#if;
+mod include;
 mod lambda;
+mod limits;
 mod loop_blocks;
+mod loop_variable;
+mod native_fn;
+mod native_method;
+mod null_safe;
 mod operations;
 mod print;
+mod range;
+mod raw_block;
+mod render_to;
 mod scripts;
+mod serialize;
+mod syntax;
+mod ternary;
+mod template;
+mod trim;
 mod r#while;
 
 use crate::macro_tests;
@@ -23,6 +44,10 @@ use crate::model::lexer::Token;
 use crate::model::stmt::*;
 use crate::model::expr::*;
 use crate::model::ctx::Ctx;
+use crate::{
+	Osmia,
+	MapLoader,
+};
 
 fn new_binary(left: Expr, op: Token, right: Expr) -> Expr {
 	let op: Option<BinaryOp> = (&op).into();
@@ -33,3 +58,29 @@ fn new_unary(op: Token, right: Expr) -> Expr {
 	let op: Option<UnaryOp> = (&op).into();
 	Unary::new(op.unwrap(), right).into()
 }
+
+/// Runs the code with the given context and the templates available to a [MapLoader].
+fn test_with_loader(
+	ctx: &str,
+	templates: Vec<(&str, &str)>,
+	code: &str,
+	expected: Result<&str, Vec<&str>>,
+) {
+	let mut loader = MapLoader::new();
+	for (name, template) in templates {
+		loader = loader.add(name, template);
+	}
+	let mut osmia = Osmia::try_from(ctx).unwrap().with_loader(loader);
+	let result = osmia.run_code(code);
+	println!("result: {:?}", result);
+	match (result, expected) {
+		(Ok(output), Ok(expected)) => assert_eq!(output, expected),
+		(Err(err), Err(pieces)) => {
+			let err = err.to_string().to_lowercase();
+			for piece in pieces {
+				assert!(err.contains(&piece.to_lowercase()), "{}", err);
+			}
+		},
+		(r, e) => panic!("Expected {:?} but got {:?}", e, r),
+	}
+}
@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use crate::{
+	Osmia,
+	NativeFn,
+};
+
+fn sum(args: &[Value]) -> Result<Value, crate::OsmiaError> {
+	let mut total = 0.0;
+	for arg in args {
+		total += arg.as_f64().ok_or("Expected a number")?;
+	}
+	Ok(json!(total))
+}
+
+#[test]
+fn register_variadic() {
+	let mut osmia = Osmia::default();
+	osmia.register_fn("sum", sum).unwrap();
+	assert_eq!(osmia.run_code("{{ sum() }}").unwrap(), "0");
+	assert_eq!(osmia.run_code("{{ sum(1, 2.5, 3) }}").unwrap(), "6.5");
+	assert_eq!(osmia.run_code("{{ sum(1 + 1, 2 * 2) }}").unwrap(), "6");
+	assert!(osmia.run_code("{{ sum(1, \"a\") }}").is_err());
+}
+
+#[test]
+fn register_capturing_closure() {
+	struct Config {
+		currency: String,
+		decimals: usize,
+	}
+	let config = Config { currency: "EUR".into(), decimals: 2 };
+	let mut osmia = Osmia::try_from(r#"{ "price": 12.5 }"#).unwrap();
+	osmia.register_fn("app.format_money", move |args| {
+		let amount = args[0].as_f64().ok_or("Expected a number")?;
+		Ok(json!(format!("{:.*} {}", config.decimals, amount, config.currency)))
+	}).unwrap();
+	assert_eq!(osmia.run_code("{{ app.format_money(price) }}").unwrap(), "12.50 EUR");
+}
+
+#[test]
+fn register_stateful_closure() {
+	let calls = Arc::new(Mutex::new(0));
+	let counter = calls.clone();
+	let mut osmia = Osmia::default();
+	osmia.register_fn("tick", move |_| {
+		let mut calls = counter.lock().unwrap();
+		*calls += 1;
+		Ok(json!(*calls))
+	}).unwrap();
+	assert_eq!(osmia.run_code("{{ tick() }}{{ tick() }}{{ tick() }}").unwrap(), "123");
+	assert_eq!(*calls.lock().unwrap(), 3);
+}
+
+#[test]
+fn register_fixed_arity() {
+	let mut osmia = Osmia::default();
+	osmia.register_native_fn("add", NativeFn::new(sum).arity(2)).unwrap();
+	assert_eq!(osmia.run_code("{{ add(1, 2) }}").unwrap(), "3");
+	assert!(osmia.run_code("{{ add(1) }}").is_err());
+}
+
+#[test]
+fn register_params() {
+	let mut osmia = Osmia::default();
+	osmia.register_native_fn("fmt", NativeFn::new(|args| {
+		Ok(json!(format!("{}|{}|{}", args[0], args[1], args[2])))
+	}).param("value").param_or("sep", json!("-")).param_or("times", json!(1))).unwrap();
+	assert_eq!(osmia.run_code("{{ fmt(1) }}").unwrap(), "1|\"-\"|1");
+	assert_eq!(osmia.run_code("{{ fmt(1, \"+\") }}").unwrap(), "1|\"+\"|1");
+	assert_eq!(osmia.run_code("{{ fmt(1, \"+\", 3) }}").unwrap(), "1|\"+\"|3");
+	let err = osmia.run_code("{{ fmt() }}").unwrap_err();
+	assert!(err.to_string().contains("value"));
+}
+
+#[test]
+fn register_spread() {
+	let mut osmia = Osmia::default();
+	osmia.register_native_fn("join", NativeFn::new(|args| {
+		let sep = args[0].as_str().ok_or("Expected a string")?;
+		let items: Vec<String> = args[1].as_array().unwrap().iter()
+			.map(|v| v.to_string())
+			.collect();
+		Ok(json!(items.join(sep)))
+	}).param("sep").spread("items")).unwrap();
+	assert_eq!(osmia.run_code("{{ join(\", \", 1, 2, 3) }}").unwrap(), "1, 2, 3");
+	assert_eq!(osmia.run_code("{{ join(\"-\", 1 + 1, [3]) }}").unwrap(), "2-[3]");
+	assert_eq!(osmia.run_code("{{ join(\"-\") }}").unwrap(), "");
+}
+
+#[test]
+fn register_returns_structures() {
+	let mut osmia = Osmia::default();
+	osmia.register_fn("user", |_| Ok(json!({ "name": "Marvin", "langs": ["rust"] }))).unwrap();
+	assert_eq!(osmia.run_code("{{ user().name }} {{ user().langs[0] }}").unwrap(), "Marvin rust");
+	assert_eq!(osmia.run_code("{{ user()?keys()?len() }}").unwrap(), "2");
+}
+
+#[test]
+fn register_nested_keys() {
+	let mut osmia = Osmia::try_from(r#"{ "app": { "name": "osmia" } }"#).unwrap();
+	osmia.register_fn("app.utils.sum", sum).unwrap();
+	osmia.register_fn("math.sum", sum).unwrap();
+	assert_eq!(osmia.run_code("{{ app.name }} {{ app.utils.sum(1, 2) }}").unwrap(), "osmia 3");
+	assert_eq!(osmia.run_code("{{ math.sum(1, 2) }} {{ math.abs(-1) }}").unwrap(), "3 1");
+	assert!(osmia.register_fn("app.name.sum", sum).is_err());
+	assert_eq!(osmia.run_code("{{ app.name }}").unwrap(), "osmia");
+}
+
+#[test]
+fn register_error() {
+	let mut osmia = Osmia::default();
+	osmia.register_fn("fail", |_| Err("Something went wrong".into())).unwrap();
+	let err = osmia.run_code("{{ fail() }}").unwrap_err();
+	assert!(err.to_string().contains("Something went wrong"));
+	osmia.register_fn("echo", |args| Ok(args[0].clone())).unwrap();
+	assert!(osmia.run_code("{{ echo(fn (x) => x) }}").is_err());
+}
+
+#[test]
+fn register_in_template() {
+	let mut osmia = Osmia::default();
+	osmia.register_native_fn("double", NativeFn::new(|args| {
+		Ok(json!(args[0].as_i64().unwrap_or(0) * 2))
+	}).arity(1)).unwrap();
+	assert_eq!(osmia.run_code("{{ f = double }}{{ f(4) }}").unwrap(), "8");
+	assert_eq!(osmia.run_code("{{ [1, 2]?map(double) }}").unwrap(), "[2, 4]");
+}
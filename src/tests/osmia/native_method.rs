@@ -0,0 +1,88 @@
+use serde_json::{json, Value};
+use crate::{
+	Osmia,
+	OsmiaError,
+	NativeFn,
+	MethodType,
+};
+
+fn slugify(args: &[Value]) -> Result<Value, OsmiaError> {
+	let s = args[0].as_str().ok_or("Expected a string")?;
+	Ok(json!(s.to_lowercase().split_whitespace().collect::<Vec<_>>().join("-")))
+}
+
+#[test]
+fn register_method() {
+	let mut osmia = Osmia::try_from(r#"{ "title": "Hello  New World" }"#).unwrap();
+	osmia.register_method(MethodType::Str, "slugify", slugify).unwrap();
+	assert_eq!(osmia.run_code("{{ title?slugify() }}").unwrap(), "hello-new-world");
+	assert_eq!(osmia.run_code("{{ title?slugify()?upper() }}").unwrap(), "HELLO-NEW-WORLD");
+	assert!(osmia.run_code("{{ 1?slugify() }}").is_err());
+}
+
+#[test]
+fn register_method_with_params() {
+	let mut osmia = Osmia::try_from(r#"{ "price": 12.5 }"#).unwrap();
+	osmia.register_native_method(MethodType::Float, "money", NativeFn::new(|args| {
+		let amount = args[0].as_f64().ok_or("Expected a number")?;
+		Ok(json!(format!("{:.2} {}", amount, args[1].as_str().unwrap_or("?"))))
+	}).param("amount").param_or("currency", json!("EUR"))).unwrap();
+	assert_eq!(osmia.run_code("{{ price?money() }}").unwrap(), "12.50 EUR");
+	assert_eq!(osmia.run_code("{{ price?money(\"USD\") }}").unwrap(), "12.50 USD");
+}
+
+#[test]
+fn register_generic_method() {
+	let mut osmia = Osmia::default();
+	osmia.register_method(MethodType::Any, "json", |args| Ok(json!(args[0].to_string()))).unwrap();
+	assert_eq!(osmia.run_code("{{ 1?json() }}").unwrap(), "1");
+	assert_eq!(osmia.run_code("{{ \"a\"?json() }}").unwrap(), "\"a\"");
+	assert_eq!(osmia.run_code("{{ [1, null]?json() }}").unwrap(), "[1,null]");
+	assert_eq!(osmia.run_code("{{ {\"a\": true}?json() }}").unwrap(), "{\"a\":true}");
+}
+
+#[test]
+fn register_range_method() {
+	let mut osmia = Osmia::default();
+	osmia.register_method(MethodType::Any, "twice", |args| Ok(json!([args[0], args[0]]))).unwrap();
+	osmia.register_method(MethodType::Range, "sum", |args| {
+		let items = args[0].as_array().ok_or("Expected an array")?;
+		Ok(json!(items.iter().filter_map(Value::as_i64).sum::<i64>()))
+	}).unwrap();
+	assert_eq!(osmia.run_code("{{ (0..3)?twice() }}").unwrap(), "[[0, 1, 2], [0, 1, 2]]");
+	assert_eq!(osmia.run_code("{{ (1..=4)?sum() }} {{ (0..10)?step(5)?sum() }}").unwrap(), "10 5");
+	assert!(osmia.run_code("{{ [1, 2]?sum() }}").is_err());
+	let err = osmia.run_code("{{ (0..1000000000000)?sum() }}").unwrap_err().to_string();
+	assert!(err.contains("can be serialized"), "{}", err);
+}
+
+#[test]
+fn register_method_shadowing() {
+	let mut osmia = Osmia::default();
+	let err = osmia.register_method(MethodType::Str, "upper", slugify).unwrap_err();
+	assert_eq!(err.info().culprit(), Some("_method.str.upper"));
+	assert!(osmia.register_method(MethodType::Any, "len", slugify).is_err());
+	assert!(osmia.register_method(MethodType::Any, "upper", slugify).is_err());
+	assert_eq!(osmia.run_code("{{ \"Hi there\"?upper() }}").unwrap(), "HI THERE");
+	osmia.register_method(MethodType::Int, "upper", |args| Ok(args[0].clone())).unwrap();
+	assert!(osmia.register_method(MethodType::Int, "upper", slugify).is_err());
+	assert_eq!(osmia.run_code("{{ 1?upper() }}").unwrap(), "1");
+}
+
+#[test]
+fn override_method() {
+	let mut osmia = Osmia::default();
+	osmia.override_method(MethodType::Str, "upper", slugify).unwrap();
+	assert_eq!(osmia.run_code("{{ \"Hi there\"?upper() }}").unwrap(), "hi-there");
+	assert_eq!(osmia.run_code("{{ \"Hi there\"?lower() }}").unwrap(), "hi there");
+	osmia.override_method(MethodType::Any, "len", NativeFn::new(|_| Ok(json!(-1))).arity(1)).unwrap();
+	assert_eq!(osmia.run_code("{{ [1, 2]?len() }}").unwrap(), "-1");
+	assert_eq!(osmia.run_code("{{ 3?len() }}").unwrap(), "-1");
+}
+
+#[test]
+fn methods_do_not_leak_between_instances() {
+	let mut osmia = Osmia::default();
+	osmia.override_method(MethodType::Str, "upper", slugify).unwrap();
+	assert_eq!(Osmia::default().run_code("{{ \"Hi there\"?upper() }}").unwrap(), "HI THERE");
+}
@@ -0,0 +1,229 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		null_coalescing_01,
+		Some("{{ a ?? 1 }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("a"),
+			Token::Whitespace,
+			Token::NullCoalesce,
+			Token::Whitespace,
+			Token::new_number("1"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Expr::Binary(Binary::new(
+			Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("a".into())).into(),
+			BinaryOp::NullCoalesce,
+			Expr::Int(1),
+		)).into()),
+		Some(vec![
+			(Ctx::new(), Ok("1")),
+			(Ctx::try_from(r#"{"a": null}"#).unwrap(), Ok("1")),
+			(Ctx::try_from(r#"{"a": 2}"#).unwrap(), Ok("2")),
+		])
+	),
+	(
+		safe_navigation_01,
+		Some("{{ a?.b?.[0] }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("a"),
+			Token::SafeDot,
+			Token::new_alpha("b"),
+			Token::SafeDot,
+			Token::ArrayStart,
+			Token::new_number("0"),
+			Token::ArrayEnd,
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some({
+			let mut var = Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("a".into()));
+			var.push_safe(JsonTreeKeyExpr::JsonTreeKey("b".into()));
+			var.push_safe(Expr::Int(0).into());
+			Expr::Variable(var).into()
+		}),
+		Some(vec![
+			(Ctx::new(), Ok("null")),
+			(Ctx::try_from(r#"{"a": {}}"#).unwrap(), Ok("null")),
+			(Ctx::try_from(r#"{"a": {"b": []}}"#).unwrap(), Ok("null")),
+			(Ctx::try_from(r#"{"a": {"b": [3]}}"#).unwrap(), Ok("3")),
+		])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		null_coalescing_chain,
+		"{{ user.nickname ?? user.name ?? \"anon\" }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"user": {"nickname": "Zaphod", "name": "Zaphod Beeblebrox"}}"#).unwrap(), Ok("Zaphod")),
+			(Ctx::try_from(r#"{"user": {"name": "Arthur Dent"}}"#).unwrap(), Ok("Arthur Dent")),
+			(Ctx::try_from(r#"{"user": {"nickname": null}}"#).unwrap(), Ok("anon")),
+			(Ctx::new(), Ok("anon")),
+		]
+	),
+	(
+		null_coalescing_keeps_falsy_values,
+		"{{ a ?? 1 }} {{ b ?? 1 }} {{ c ?? 1 }} {{ d ?? 1 }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"a": false, "b": 0, "c": "", "d": []}"#).unwrap(), Ok("false 0  []"))]
+	),
+	(
+		null_coalescing_lazy,
+		"{{fn f}}{{ n = n + 1 }}{{return n}}{{done}}{{ n = 0 }}{{ 1 ?? f() }} {{ null ?? f() }} {{ n }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1 1 1"))]
+	),
+	(
+		null_coalescing_precedence,
+		"{{ a ?? 1 + 2 }} {{ a ?? false || true }} {{ (a ?? 1) + 2 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("3 true 3"))]
+	),
+	(
+		null_coalescing_method_call,
+		"{{ name?upper() ?? \"-\" }} {{ (missing ?? \"-\")?upper() }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"name": "marvin"}"#).unwrap(), Ok("MARVIN -"))]
+	),
+	(
+		null_coalescing_method_call_error,
+		"{{ missing?upper() ?? \"-\" }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["missing"]))]
+	),
+	(
+		null_coalescing_function_error,
+		"{{fn g}}{{ missing.key }}{{done}}{{ g() ?? \"fallback\" }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["missing"]))]
+	),
+	(
+		null_coalescing_expression_error,
+		"{{ (1 + nope) ?? 5 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["nope"]))]
+	),
+	(
+		null_coalescing_key_error,
+		"{{ arr[nope] ?? 7 }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": [1]}"#).unwrap(), Err(vec!["nope"]))]
+	),
+	(
+		null_coalescing_missing_key,
+		"{{ (a.b) ?? 5 }} {{ obj.b ?? 6 }} {{ {\"a\": 1}.b ?? 7 }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"obj": {"a": 1}}"#).unwrap(), Ok("5 6 7"))]
+	),
+	(
+		null_coalescing_index_out_of_bounds,
+		"{{ arr[i] ?? 6 }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": [1], "i": 2}"#).unwrap(), Err(vec!["out of bounds"]))]
+	),
+	(
+		null_coalescing_type_mismatch,
+		"{{ name.first ?? \"-\" }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"name": "marvin"}"#).unwrap(), Err(vec!["cannot access a value"]))]
+	),
+	(
+		null_coalescing_key_in_array,
+		"{{ arr.first ?? \"-\" }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": [1]}"#).unwrap(), Err(vec!["cannot get by key from an array"]))]
+	),
+	(
+		null_coalescing_runtime_error,
+		"{{ 1 / 0 ?? 1 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["cannot divide"]))]
+	),
+	(
+		safe_navigation_missing_intermediate,
+		"{{ user?.address?.city ?? \"unknown\" }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"user": {"address": {"city": "Madrid"}}}"#).unwrap(), Ok("Madrid")),
+			(Ctx::try_from(r#"{"user": {"address": null}}"#).unwrap(), Ok("unknown")),
+			(Ctx::try_from(r#"{"user": {}}"#).unwrap(), Ok("unknown")),
+			(Ctx::new(), Ok("unknown")),
+		]
+	),
+	(
+		safe_navigation_short_circuit,
+		"{{ user?.address.city }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"user": {}}"#).unwrap(), Ok("null")),
+			(Ctx::try_from(r#"{"user": {"address": {}}}"#).unwrap(), Err(vec!["city", "not found"])),
+		]
+	),
+	(
+		safe_navigation_only_after_marker,
+		"{{ user.address?.city }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"user": {"address": {}}}"#).unwrap(), Ok("null")),
+			(Ctx::try_from(r#"{"user": {}}"#).unwrap(), Ok("null")),
+		]
+	),
+	(
+		safe_navigation_index,
+		"{{ arr?.[5] }} {{ arr?.[i] }} {{ arr[0]?.name }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"arr": [{"name": "a"}], "i": 0}"#).unwrap(), Ok("null {\"name\": \"a\"} a"))]
+	),
+	(
+		safe_navigation_expression,
+		"{{ {\"a\": 1}?.b }} {{ [1, 2]?.[1] }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("null 2"))]
+	),
+	(
+		safe_navigation_call_result,
+		"{{fn f}}{{return null}}{{done}}{{fn g}}{{return {\"x\": 1}}}{{done}}{{ f()?.x }} {{ g()?.x }} {{ g()?.y ?? 2 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("null 1 2"))]
+	),
+);
+
+#[test]
+fn safe_navigation_cannot_assign() {
+	let mut osmia = crate::Osmia::default();
+	let err = osmia.run_code("{{ a?.b = 1 }}").unwrap_err().to_string();
+	assert!(err.contains("Safe navigation cannot be used to assign"), "{}", err);
+	assert!(osmia.run_code("{{capture a?.b}}x{{done}}").is_err());
+}
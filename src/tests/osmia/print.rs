@@ -113,3 +113,55 @@ macro_tests!(
 		])
 	)
 );
+
+#[test]
+fn print_sink_buffer() {
+	let mut osmia = crate::Osmia::try_from(r#"{"name": "Marvin"}"#).unwrap()
+		.with_print_sink(crate::PrintSink::Buffer);
+	let (output, printed) = osmia.run_code_with_prints(
+		"a{{for i in [1, 2]}}{{print i}}{{done}}{{print name?upper()}}{{print {\"a\": [1]} }}b"
+	).unwrap();
+	assert_eq!(output, "ab");
+	assert_eq!(printed, vec!["1", "2", "MARVIN", "{\"a\": [1]}"]);
+	let (_, printed) = osmia.run_code_with_prints("{{print 3}}").unwrap();
+	assert_eq!(printed, vec!["3"]);
+}
+
+#[test]
+fn print_sink_buffer_render_to() {
+	let mut osmia = crate::Osmia::default().with_print_sink(crate::PrintSink::Buffer);
+	let mut output: Vec<u8> = Vec::new();
+	let printed = osmia.render_to_with_prints("a{{print 1}}{{for i in [2, 3]}}{{ i }}{{print i}}{{done}}", &mut output).unwrap();
+	assert_eq!(output, b"a23");
+	assert_eq!(printed, vec!["1", "2", "3"]);
+
+	let mut output: Vec<u8> = Vec::new();
+	osmia.render_to("b{{print 1}}", &mut output).unwrap();
+	assert_eq!(output, b"b");
+	assert_eq!(osmia.render_to_with_prints("{{print 4}}", &mut Vec::new()).unwrap(), vec!["4"]);
+	assert!(osmia.render_to_with_prints("{{print 5}}{{ missing }}", &mut Vec::new()).is_err());
+}
+
+#[test]
+fn print_sink_not_buffered() {
+	let mut osmia = crate::Osmia::default().with_print_sink(crate::PrintSink::Stderr);
+	assert_eq!(osmia.run_code_with_prints("{{print 1}}").unwrap(), ("".into(), vec![]));
+}
+
+#[test]
+fn print_sink_callback() {
+	use std::sync::{Arc, Mutex};
+
+	let lines = Arc::new(Mutex::new(Vec::new()));
+	let sink_lines = lines.clone();
+	let osmia = crate::Osmia::default()
+		.with_print_sink(crate::PrintSink::callback(move |l| sink_lines.lock().unwrap().push(l.to_string())));
+	let template = osmia.compile("{{fn f; x}}{{print x}}{{done}}{{ y = f(1) }}{{print 2}}").unwrap();
+	assert_eq!(template.render(&mut Ctx::new()).unwrap(), "");
+	template.render_to(&mut Ctx::new(), &mut Vec::new()).unwrap();
+	assert_eq!(*lines.lock().unwrap(), vec!["1", "2", "1", "2"]);
+
+	let template = template.with_print_sink(crate::PrintSink::Buffer);
+	assert_eq!(template.render_with_prints(&mut Ctx::new()).unwrap().1, vec!["1", "2"]);
+	assert_eq!(lines.lock().unwrap().len(), 4);
+}
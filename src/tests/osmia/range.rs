@@ -0,0 +1,231 @@
+use super::*;
+use crate::{
+	Osmia,
+	OsmiaError,
+	Limits,
+	LimitKind,
+};
+
+macro_tests!(
+	test,
+	(
+		range_01,
+		Some("{{ 0..3 }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_number("0"),
+			Token::Range,
+			Token::new_number("3"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(new_binary(Expr::Int(0), Token::Range, Expr::Int(3)).into()),
+		Some(vec![(Ctx::new(), Ok("0..3"))])
+	),
+	(
+		range_02,
+		Some("{{ 1..=n }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_number("1"),
+			Token::RangeInclusive,
+			Token::new_alpha("n"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(new_binary(
+			Expr::Int(1),
+			Token::RangeInclusive,
+			Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("n".into())).into(),
+		).into()),
+		Some(vec![(Ctx::try_from(r#"{"n": 3}"#).unwrap(), Ok("1..4"))])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		range_for,
+		"{{for i in 0..5}}{{i}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("0 1 2 3 4 "))]
+	),
+	(
+		range_for_inclusive,
+		"{{for i in 1..=n}}{{i}} {{done}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"n": 3}"#).unwrap(), Ok("1 2 3 ")),
+			(Ctx::try_from(r#"{"n": 1}"#).unwrap(), Ok("1 ")),
+			(Ctx::try_from(r#"{"n": 0}"#).unwrap(), Ok("")),
+		]
+	),
+	(
+		range_for_empty,
+		"{{for i in 5..2}}{{i}}{{done}}{{for i in 3..3}}{{i}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok(""))]
+	),
+	(
+		range_for_negative,
+		"{{for i in -2..2}}{{i}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("-2 -1 0 1 "))]
+	),
+	(
+		range_for_step,
+		"{{for i in (0..100)?step(25)}}{{i}} {{done}}|{{for i in (0..=10)?step(5)}}{{i}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("0 25 50 75 |0 5 10 "))]
+	),
+	(
+		range_for_reverse,
+		"{{for i in (0..4)?reverse()}}{{i}} {{done}}|{{for i in (0..10)?step(3)?reverse()}}{{i}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("3 2 1 0 |9 6 3 0 "))]
+	),
+	(
+		range_for_reverse_step,
+		"{{for i in (0..10)?reverse()?step(3)}}{{i}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("9 6 3 0 "))]
+	),
+	(
+		range_for_key,
+		"{{for k, v in 5..8}}{{k}}:{{v}} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("0:5 1:6 2:7 "))]
+	),
+	(
+		range_for_variable,
+		"{{ r = 1..4 }}{{for i in r}}{{i}}{{done}} {{ r?len() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("123 3"))]
+	),
+	(
+		range_for_lazy,
+		"{{for i in 0..9000000000000000000}}{{if i == 3}}{{break}}{{fi}}{{i}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("012"))]
+	),
+	(
+		range_loop_variable,
+		"{{for i in (0..6)?step(2)}}{{loop.index}}/{{loop.length}}{{if !loop.last}} {{fi}}{{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1/3 2/3 3/3"))]
+	),
+	(
+		range_precedence,
+		"{{ 1 + 1..2 * 3 }} {{ (0..3) == (0..=2) }} {{ 0..3 == 0..3 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("2..6 true true"))]
+	),
+	(
+		range_len,
+		"{{ (0..10)?len() }} {{ (0..=10)?len() }} {{ (5..2)?len() }} {{ (0..10)?step(3)?len() }} {{ (0..10)?step(20)?len() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("10 11 0 4 1"))]
+	),
+	(
+		range_len_boundaries,
+		"{{ (0..9223372036854775807)?len() }} {{ (-9223372036854775807..0)?len() }} {{ (-1..=9223372036854775805)?len() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("9223372036854775807 9223372036854775807 9223372036854775807"))]
+	),
+	(
+		range_len_overflow,
+		"{{ (0..=9223372036854775807)?len() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["length of the range", "does not fit in an int"]))]
+	),
+	(
+		range_len_overflow_negative,
+		"{{ (-9223372036854775807..9223372036854775807)?len() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["does not fit in an int"]))]
+	),
+	(
+		range_contains,
+		"{{ (0..10)?contains(0) }} {{ (0..10)?contains(10) }} {{ (0..=10)?contains(10) }} {{ (0..10)?contains(-1) }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("true false true false"))]
+	),
+	(
+		range_contains_step,
+		"{{ (0..10)?step(3)?contains(6) }} {{ (0..10)?step(3)?contains(7) }} {{ (0..10)?step(3)?reverse()?contains(9) }} {{ (0..3)?contains(\"1\") }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("true false true false"))]
+	),
+	(
+		range_display,
+		"{{ 0..10 }} {{ 5..0 }} {{ (0..10)?step(3) }} {{ (0..3)?reverse() }} {{ (0..10)?step(3)?reverse() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("0..10 0..0 (0..10)?step(3) (0..3)?reverse() (0..10)?step(3)?reverse()"))]
+	),
+	(
+		range_methods,
+		"{{ (0..3)?type() }} {{ (0..3)?has_content() }} {{ (0..0)?has_content() }} {{ (0..0)?to_bool() }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("range true false true"))]
+	),
+	(
+		range_invalid_bounds,
+		"{{ 0..1.5 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["bounds of a range must be integers", "int and float"]))]
+	),
+	(
+		range_invalid_string_bounds,
+		"{{ \"a\"..\"c\" }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["bounds of a range must be integers", "string and string"]))]
+	),
+	(
+		range_invalid_step,
+		"{{ (0..10)?step(0) }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["step of a range must be a positive integer", "0"]))]
+	),
+	(
+		range_negative_step,
+		"{{ (0..10)?step(-1) }}",
+		None,
+		None,
+		vec![(Ctx::new(), Err(vec!["step of a range must be a positive integer", "-1"]))]
+	),
+);
+
+#[test]
+fn range_fuel() {
+	let result = Osmia::default()
+		.with_limits(Limits::new().with_fuel(1000))
+		.run_code("{{for i in 0..9000000000000000000}}{{ i }}{{done}}");
+	assert!(matches!(result, Err(OsmiaError::Limit(LimitKind::Fuel, _))), "{:?}", result);
+}
@@ -0,0 +1,148 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		raw_block_01,
+		Some("{{raw}}{{ a }}{{endraw}}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::RawBlock,
+			Token::StmtEnd,
+			Token::new_raw("{{ a }}"),
+			Token::StmtStart,
+			Token::EndRawBlock,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::new_raw("{{ a }}")),
+		Some(vec![(Ctx::new(), Ok("{{ a }}"))])
+	),
+	(
+		raw_block_02,
+		Some("{{raw}}{{endraw}}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::RawBlock,
+			Token::StmtEnd,
+			Token::StmtStart,
+			Token::EndRawBlock,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Stmt::Block(vec![].into())),
+		Some(vec![(Ctx::new(), Ok(""))])
+	),
+	(
+		raw_block_03,
+		Some("a: {{ a }}\n{{raw  }}\n  {{if a}} {{# }}\n\n {{fi}\n{{endraw }}\n"),
+		None,
+		None,
+		Some(vec![
+			(Ctx::try_from(r#"{"a": 1}"#).unwrap(), Ok("a: 1\n  {{if a}} {{# }}\n\n {{fi}\n")),
+		])
+	),
+	(
+		raw_block_04,
+		Some("  {{raw}}\n  \n\t{{endraw}}\n"),
+		Some(vec![
+			Token::new_non_printable("  "),
+			Token::StmtStart,
+			Token::RawBlock,
+			Token::StmtEnd,
+			Token::NewLineNonPrintable,
+			Token::new_non_printable("  "),
+			Token::NewLineNonPrintable,
+			Token::new_non_printable("\t"),
+			Token::StmtStart,
+			Token::EndRawBlock,
+			Token::StmtEnd,
+			Token::NewLineNonPrintable,
+			Token::Eof
+		]),
+		Some(Stmt::Block(vec![
+			Stmt::NonPrintable("  ".into()),
+			Stmt::Block(vec![
+				Stmt::NewLineNonPrintable,
+				Stmt::new_raw("  "),
+				Stmt::NewLine,
+				Stmt::NonPrintable("\t".into()),
+			].into()),
+			Stmt::NewLineNonPrintable,
+		].into())),
+		Some(vec![(Ctx::new(), Ok("  \n"))])
+	),
+	(
+		escaped_delimiter_01,
+		Some("\\{{ a }} {{ a }}"),
+		Some(vec![
+			Token::new_raw("{{ a }} "),
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_alpha("a"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		None,
+		Some(vec![(Ctx::try_from(r#"{"a": 1}"#).unwrap(), Ok("{{ a }} 1"))])
+	),
+	(
+		escaped_delimiter_02,
+		Some("a\\{{\\{{}} \\ \\{ }}\\"),
+		Some(vec![
+			Token::new_raw("a{{{{}} \\ \\{ }}\\"),
+			Token::Eof
+		]),
+		None,
+		Some(vec![(Ctx::new(), Ok("a{{{{}} \\ \\{ }}\\"))])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		raw_block_in_loop,
+		"{{for i in [1, 2]}}{{raw}}{{ i }}{{endraw}}={{ i }} {{done}}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("{{ i }}=1 {{ i }}=2 "))]
+	),
+	(
+		raw_block_whitespace,
+		"{{ raw }}{{ a }}{{ endraw }} {{raw}}x{{ endraw }} {{ raw}}y{{endraw }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("{{ a }} x y"))]
+	),
+	(
+		raw_variable,
+		"{{ raw.a }} {{ endraw.a }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"raw": {"a": 1}, "endraw": {"a": 2}}"#).unwrap(), Ok("1 2"))]
+	),
+	(
+		escaped_backslash,
+		"C:\\\\{{ f }} C:\\{{ f }} \\\\",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"f": "x"}"#).unwrap(), Ok("C:\\x C:{{ f }} \\\\"))]
+	),
+	(
+		raw_block_in_comment,
+		"a{{# {{raw}} }}b",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("ab"))]
+	),
+);
+
+#[test]
+fn raw_block_errors() {
+	let mut osmia = crate::Osmia::default();
+	let err = osmia.run_code("{{raw}}{{ a }}").unwrap_err().to_string();
+	assert!(err.contains("Unclosed raw block. Expected '{{endraw}}'"), "{}", err);
+	let err = osmia.run_code("{{ endraw }}").unwrap_err().to_string();
+	assert!(err.contains("Unexpected '{{endraw}}' without an opening '{{raw}}'"), "{}", err);
+}
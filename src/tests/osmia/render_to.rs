@@ -0,0 +1,117 @@
+use std::io::Write;
+use crate::{
+	Osmia,
+	Ctx,
+	MapLoader,
+	Limits,
+};
+
+#[derive(Default)]
+struct Chunks {
+	chunks: Vec<String>,
+	flushed: bool,
+}
+
+impl Write for Chunks {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.chunks.push(String::from_utf8(buf.to_vec()).unwrap());
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		self.flushed = true;
+		Ok(())
+	}
+}
+
+struct Broken;
+
+impl Write for Broken {
+	fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+		Err(std::io::Error::other("disk full"))
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+fn render(code: &str) -> String {
+	let mut output: Vec<u8> = Vec::new();
+	Osmia::default().render_to(code, &mut output).unwrap();
+	String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn same_output_as_run_code() {
+	for code in [
+		"Hello world",
+		"{{ 1 + 2 }}",
+		"a\n{{ b = 2 }}\nc{{ b }}",
+		"{{for i in [1, 2, 3]}}{{ i }},{{done}}",
+		"{{ i = 0 }}{{while i < 3}}{{ i = i + 1 }}{{ i }}{{if i == 2}}{{break}}{{fi}}{{done}}",
+		"{{fn f; x}}<{{ x }}>{{done}}[{{ f(1) }}{{ f(2) }}]",
+		"{{fn f; x}}ignored{{return x * 2}}{{done}}{{ f(2) }}",
+		"{{fn f}}{{for i in [1, 2]}}{{ i }}{{done}}{{done}}{{ f()?len() }}",
+		"{{ [1, 2]?map(fn (x) => x * 3) }}",
+	] {
+		assert_eq!(render(code), Osmia::default().run_code(code).unwrap(), "code: {}", code);
+	}
+}
+
+#[test]
+fn writes_while_rendering() {
+	let template = Osmia::default().compile("a{{for i in [1, 2, 3]}}{{ i }}{{done}}b").unwrap();
+	let mut chunks = Chunks::default();
+	template.render_to(&mut Ctx::new(), &mut chunks).unwrap();
+	assert_eq!(chunks.chunks, vec!["a", "1", "2", "3", "b"]);
+	assert!(chunks.flushed);
+}
+
+#[test]
+fn function_output_is_not_streamed() {
+	let template = Osmia::default().compile("{{fn f}}x{{done}}{{ f()?upper() }}").unwrap();
+	let mut chunks = Chunks::default();
+	template.render_to(&mut Ctx::new(), &mut chunks).unwrap();
+	assert_eq!(chunks.chunks, vec!["X"]);
+}
+
+#[test]
+fn include_is_streamed() {
+	let mut osmia = Osmia::default().with_loader(MapLoader::new().add("item", "<{{ item }}>"));
+	let mut chunks = Chunks::default();
+	osmia.render_to("{{for item in [1, 2]}}{{include \"item\"}}{{done}}", &mut chunks).unwrap();
+	assert_eq!(chunks.chunks.concat(), "<1><2>");
+	assert_eq!(chunks.chunks.len(), 6);
+}
+
+#[test]
+fn output_before_error_is_written() {
+	let mut chunks = Chunks::default();
+	let err = Osmia::default().render_to("abc{{ 1 / 0 }}def", &mut chunks).unwrap_err();
+	assert!(err.to_string().contains("{{ 1 / 0 }}"));
+	assert_eq!(chunks.chunks, vec!["abc"]);
+}
+
+#[test]
+fn output_limit() {
+	let mut osmia = Osmia::default().with_limits(Limits::new().with_output(5));
+	let mut chunks = Chunks::default();
+	assert!(osmia.render_to("{{while true}}ab{{done}}", &mut chunks).is_err());
+	assert_eq!(chunks.chunks.concat(), "abab");
+}
+
+#[test]
+fn write_error() {
+	let err = Osmia::default().render_to("Hello", &mut Broken).unwrap_err();
+	assert!(err.to_string().contains("disk full"));
+}
+
+#[test]
+fn render_to_keeps_ctx() {
+	let mut osmia = Osmia::default();
+	let mut output: Vec<u8> = Vec::new();
+	osmia.render_to("{{ total = 40 + 2 }}", &mut output).unwrap();
+	assert!(output.is_empty());
+	assert_eq!(osmia.get::<i64>("total").unwrap(), 42);
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use crate::{
+	Osmia,
+	OsmiaError,
+	Ctx,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct User {
+	name: String,
+	age: u32,
+	langs: Vec<String>,
+	address: Option<Address>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Address {
+	city: String,
+}
+
+fn marvin() -> User {
+	User {
+		name: "Marvin".into(),
+		age: 42,
+		langs: vec!["rust".into(), "c".into()],
+		address: Some(Address { city: "Madrid".into() }),
+	}
+}
+
+#[test]
+fn from_serialize_struct() {
+	let mut osmia = Osmia::from_serialize(&marvin()).unwrap();
+	assert_eq!(
+		osmia.run_code("{{ name }} ({{ age }}) {{ langs[1] }} {{ address.city }}").unwrap(),
+		"Marvin (42) c Madrid"
+	);
+}
+
+#[test]
+fn from_serialize_map() {
+	let data = HashMap::from([("a", 1.5), ("b", 2.0)]);
+	let mut osmia = Osmia::from_serialize(&data).unwrap();
+	assert_eq!(osmia.run_code("{{ a + b }}").unwrap(), "3.5");
+}
+
+#[test]
+fn from_serialize_array() {
+	let mut osmia = Osmia::from_serialize(&[1, 2, 3]).unwrap();
+	assert_eq!(osmia.run_code("{{ ctx?len() }}").unwrap(), "3");
+}
+
+#[test]
+fn from_serialize_invalid() {
+	assert!(Osmia::from_serialize(&42).is_err());
+	assert!(Osmia::from_serialize("text").is_err());
+	assert!(Osmia::from_serialize(&HashMap::from([((1, 2), 3)])).is_err());
+}
+
+#[test]
+fn from_json_value() {
+	let mut osmia = Osmia::try_from(json!({
+		"user": { "name": "Marvin", "admin": true, "email": null }
+	})).unwrap();
+	assert_eq!(
+		osmia.run_code("{{ user.name }} {{ user.admin }} {{ user.email }}").unwrap(),
+		"Marvin true null"
+	);
+	assert!(Osmia::try_from(json!("text")).is_err());
+}
+
+#[test]
+fn ctx_from_serialize() {
+	let mut ctx = Ctx::from_serialize(&marvin()).unwrap();
+	let template = Osmia::default().compile("{{ address.city }}").unwrap();
+	assert_eq!(template.render(&mut ctx).unwrap(), "Madrid");
+}
+
+#[test]
+fn set_value() {
+	let mut osmia = Osmia::default();
+	osmia.set("user", &marvin()).unwrap();
+	osmia.set("total", &10).unwrap();
+	osmia.set("tags", &json!(["a", "b"])).unwrap();
+	assert_eq!(
+		osmia.run_code("{{ user.name }} {{ total }} {{ tags[1] }}").unwrap(),
+		"Marvin 10 b"
+	);
+}
+
+#[test]
+fn set_nested() {
+	let mut osmia = Osmia::from_serialize(&marvin()).unwrap();
+	osmia.set("address.city", "Paris").unwrap();
+	osmia.set("langs[0]", &json!({ "name": "go" })).unwrap();
+	assert_eq!(osmia.run_code("{{ address.city }} {{ langs[0].name }}").unwrap(), "Paris go");
+}
+
+#[test]
+fn set_overrides() {
+	let mut osmia = Osmia::from_serialize(&marvin()).unwrap();
+	osmia.set("address", &None::<Address>).unwrap();
+	assert_eq!(osmia.run_code("{{ address }}").unwrap(), "null");
+}
+
+#[test]
+fn set_invalid() {
+	let mut osmia = Osmia::from_serialize(&marvin()).unwrap();
+	assert!(osmia.set("name.first", "Marvin").is_err());
+	assert!(osmia.set("langs.first", "go").is_err());
+	assert!(osmia.set("[", "go").is_err());
+}
+
+#[test]
+fn get_value() {
+	let mut osmia = Osmia::from_serialize(&marvin()).unwrap();
+	osmia.run_code("{{ total = age * 2 }}{{ ratio = age / 8.0 }}{{ first = langs[0]?upper() }}").unwrap();
+	assert_eq!(osmia.get::<i64>("total").unwrap(), 84);
+	assert_eq!(osmia.get::<f64>("ratio").unwrap(), 5.25);
+	assert_eq!(osmia.get::<String>("first").unwrap(), "RUST");
+	assert_eq!(osmia.get::<Vec<String>>("langs").unwrap(), vec!["rust", "c"]);
+	assert_eq!(osmia.get::<Option<Address>>("address").unwrap(), Some(Address { city: "Madrid".into() }));
+	assert_eq!(osmia.get::<serde_json::Value>("address").unwrap(), json!({ "city": "Madrid" }));
+}
+
+#[test]
+fn get_range() {
+	let mut osmia = Osmia::default();
+	osmia.run_code("{{ rr = 0..3 }}{{ steps = (0..=10)?step(5)?reverse() }}{{ empty = 3..0 }}").unwrap();
+	assert_eq!(osmia.get::<Vec<i64>>("rr").unwrap(), vec![0, 1, 2]);
+	assert_eq!(osmia.get::<Vec<i64>>("steps").unwrap(), vec![10, 5, 0]);
+	assert_eq!(osmia.get::<Vec<i64>>("empty").unwrap(), Vec::<i64>::new());
+	assert_eq!(osmia.get::<serde_json::Value>("rr").unwrap(), json!([0, 1, 2]));
+}
+
+#[test]
+fn get_huge_range() {
+	let mut osmia = Osmia::default();
+	osmia.run_code("{{ r = 0..1000000000000 }}").unwrap();
+	let err = osmia.get::<Vec<i64>>("r").unwrap_err().to_string();
+	assert!(err.contains("more than the 1048576 that can be serialized"), "{}", err);
+	assert!(osmia.get::<i64>("r").is_err());
+	assert_eq!(osmia.run_code("{{ r?len() }}").unwrap(), "1000000000000");
+}
+
+#[test]
+fn get_round_trip() {
+	let mut osmia = Osmia::default();
+	osmia.set("user", &marvin()).unwrap();
+	assert_eq!(osmia.get::<User>("user").unwrap(), marvin());
+	osmia.set("user.address", &None::<Address>).unwrap();
+	assert_eq!(osmia.get::<User>("user").unwrap().address, None);
+}
+
+#[test]
+fn get_missing() {
+	let osmia = Osmia::from_serialize(&marvin()).unwrap();
+	match osmia.get::<String>("address.street").unwrap_err() {
+		OsmiaError::Ctx(info) => assert_eq!(info.culprit(), Some("address.street")),
+		e => panic!("Unexpected error: {}", e),
+	}
+	assert!(osmia.get::<String>("langs[4]").is_err());
+	assert!(osmia.get::<String>("[").is_err());
+}
+
+#[test]
+fn get_type_mismatch() {
+	let osmia = Osmia::from_serialize(&marvin()).unwrap();
+	for (key, err) in [
+		("name", osmia.get::<i64>("name").unwrap_err()),
+		("langs", osmia.get::<Vec<i64>>("langs").unwrap_err()),
+		("address", osmia.get::<User>("address").unwrap_err()),
+		("math", osmia.get::<serde_json::Value>("math").unwrap_err()),
+	] {
+		match err {
+			OsmiaError::Conversion(info) => assert_eq!(info.culprit(), Some(key)),
+			e => panic!("Unexpected error: {}", e),
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use crate::{
+	Osmia,
+	Syntax,
+	MapLoader,
+};
+
+const CTX: &str = r#"{ "name": "Marvin", "items": [1, 2, 3] }"#;
+
+fn run(syntax: Syntax, code: &str) -> String {
+	Osmia::try_from(CTX).unwrap().with_syntax(syntax).run_code(code).unwrap()
+}
+
+#[test]
+fn go_template() {
+	assert_eq!(
+		run(Syntax::new("<%", "%>"), "name: {{ .Values.name | quote }} # <% name %>"),
+		"name: {{ .Values.name | quote }} # Marvin"
+	);
+}
+
+#[test]
+fn latex() {
+	assert_eq!(
+		run(Syntax::new("<<", ">>"), "\\textbf{<< name?upper() >>}\\\\{<<for i in items>><< i >>,<<done>>}"),
+		"\\textbf{MARVIN}\\\\{1,2,3,}"
+	);
+}
+
+#[test]
+fn statements() {
+	let code = "[[fn greet; who]]Hi [[ who ]][[done]]\
+		[[if items?len() > 2]][[ greet(name) ]][[else]]no[[fi]]\
+		[[ x = {\"a\": [items[0]]} ]][[ x.a[0] ]]";
+	assert_eq!(run(Syntax::new("[[", "]]"), code), "Hi Marvin1");
+}
+
+#[test]
+fn comments() {
+	assert_eq!(run(Syntax::new("<%", "%>").with_comment("--"), "a<%-- <% name %> --%>b"), "ab");
+	assert_eq!(run(Syntax::default().with_comment("!"), "a{{! comment }}b"), "ab");
+}
+
+#[test]
+fn non_printable_lines() {
+	assert_eq!(
+		run(Syntax::new("<%", "%>"), "<%for i in items%>\n  <% i %>\n<%done%>\n"),
+		"  1\n  2\n  3\n"
+	);
+}
+
+#[test]
+fn includes() {
+	let mut osmia = Osmia::try_from(CTX).unwrap()
+		.with_syntax(Syntax::new("<%", "%>"))
+		.with_loader(MapLoader::new()
+			.add("base", "<html>{{ raw }}<%block body%><%done%></html>")
+			.add("item", "[<% i %>]")
+		);
+	assert_eq!(
+		osmia.run_code("<%extends \"base\"%><%block body%><%for i in items%><%include \"item\"%><%done%><%done%>").unwrap(),
+		"<html>{{ raw }}[1][2][3]</html>"
+	);
+}
+
+#[test]
+fn compiled_template() {
+	let osmia = Osmia::default().with_syntax(Syntax::new("<%", "%>"));
+	let template = osmia.compile("<% name %> {{ name }}").unwrap();
+	assert_eq!(template.render_with(CTX).unwrap(), "Marvin {{ name }}");
+}
+
+#[test]
+fn syntax_errors() {
+	let mut osmia = Osmia::try_from(CTX).unwrap().with_syntax(Syntax::new("<%", "%>"));
+	let err = osmia.run_code("<% name ").unwrap_err().to_string();
+	assert!(err.contains("Expected '%>'"), "{}", err);
+	let mut osmia = Osmia::default().with_syntax(Syntax::new("", ""));
+	assert!(osmia.run_code("text").is_err());
+}
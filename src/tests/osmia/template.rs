@@ -0,0 +1,55 @@
+use crate::{
+	Osmia,
+	MapLoader,
+	Ctx,
+};
+
+#[test]
+fn template_render_many() {
+	let template = Osmia::default().compile("{{ name }}: {{ age + 1 }}").unwrap();
+	for (name, age) in [("Marvin", 42), ("R2D2", 33), ("C3PO", 112)] {
+		let json = format!(r#"{{ "name": "{}", "age": {} }}"#, name, age);
+		assert_eq!(template.render_with(&json).unwrap(), format!("{}: {}", name, age + 1));
+	}
+}
+
+#[test]
+fn template_render_ctx() {
+	let template = Osmia::default().compile("{{ i = i + 1 }}{{ i }}").unwrap();
+	let mut ctx = Ctx::try_from(r#"{ "i": 0 }"#).unwrap();
+	assert_eq!(template.render(&mut ctx).unwrap(), "1");
+	assert_eq!(template.render(&mut ctx).unwrap(), "2");
+	let mut other = Ctx::try_from(r#"{ "i": 10 }"#).unwrap();
+	assert_eq!(template.render(&mut other).unwrap(), "11");
+}
+
+#[test]
+fn template_keeps_loader() {
+	let loader = MapLoader::new()
+		.add("base", "[{{block body}}{{done}}]")
+		.add("item", "<{{ item }}>");
+	let osmia = Osmia::default().with_loader(loader);
+	let template = osmia.compile(
+		"{{extends \"base\"}}{{block body}}{{for item in items}}{{include \"item\"}}{{done}}{{done}}"
+	).unwrap();
+	drop(osmia);
+	assert_eq!(template.render_with(r#"{ "items": [1, 2] }"#).unwrap(), "[<1><2>]");
+	assert_eq!(template.render_with(r#"{ "items": [] }"#).unwrap(), "[]");
+}
+
+#[test]
+fn template_compile_error() {
+	let osmia = Osmia::default();
+	assert!(osmia.compile("{{ 1 + }}").is_err());
+	assert!(osmia.compile("{{ @ }}").is_err());
+}
+
+#[test]
+fn template_render_error() {
+	let template = Osmia::default().compile("Hi\n{{ user.name }}").unwrap();
+	let err = template.render_with(r#"{ "user": {} }"#).unwrap_err();
+	assert_eq!(err.location().unwrap().line(), 2);
+	assert!(err.to_string().contains("{{ user.name }}"));
+	assert_eq!(template.render_with(r#"{ "user": { "name": "Marvin" } }"#).unwrap(), "Hi\nMarvin");
+	assert!(template.render_with("[").is_err());
+}
@@ -0,0 +1,132 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		ternary_01,
+		Some("{{ 1 if a else 2 }}"),
+		Some(vec![
+			Token::StmtStart,
+			Token::Whitespace,
+			Token::new_number("1"),
+			Token::Whitespace,
+			Token::If,
+			Token::Whitespace,
+			Token::new_alpha("a"),
+			Token::Whitespace,
+			Token::Else,
+			Token::Whitespace,
+			Token::new_number("2"),
+			Token::Whitespace,
+			Token::StmtEnd,
+			Token::Eof
+		]),
+		Some(Expr::Ternary(Ternary::new(
+			Variable::from_name(JsonTreeKeyExpr::JsonTreeKey("a".into())).into(),
+			Expr::Int(1),
+			Expr::Int(2),
+		)).into()),
+		Some(vec![
+			(Ctx::try_from(r#"{"a": true}"#).unwrap(), Ok("1")),
+			(Ctx::try_from(r#"{"a": false}"#).unwrap(), Ok("2")),
+			(Ctx::try_from(r#"{"a": null}"#).unwrap(), Ok("2")),
+		])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		ternary_inline,
+		"You have {{ n }} item{{ \"\" if n == 1 else \"s\" }}.",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"n": 1}"#).unwrap(), Ok("You have 1 item.")),
+			(Ctx::try_from(r#"{"n": 3}"#).unwrap(), Ok("You have 3 items.")),
+		]
+	),
+	(
+		ternary_chain,
+		"{{ \"neg\" if n < 0 else \"zero\" if n == 0 else \"pos\" }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"n": -4}"#).unwrap(), Ok("neg")),
+			(Ctx::try_from(r#"{"n": 0}"#).unwrap(), Ok("zero")),
+			(Ctx::try_from(r#"{"n": 7}"#).unwrap(), Ok("pos")),
+		]
+	),
+	(
+		ternary_lazy,
+		"{{ 1 if true else missing.key }} {{ missing.key if false else 1 / 1 }} {{ 1 if true else 1 / 0 }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1 1 1"))]
+	),
+	(
+		ternary_lazy_side_effects,
+		"{{fn f; x}}{{ n = n + x }}{{return x}}{{done}}{{ n = 0 }}{{ f(1) if n == 0 else f(10) }} {{ n }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("1 1"))]
+	),
+	(
+		ternary_precedence,
+		"{{ 1 + 2 if a || b else 3 * 4 }} {{ (1 if a else 2) + 10 }} {{ x ?? 5 if a else 6 }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"a": false, "b": true}"#).unwrap(), Ok("3 12 6"))]
+	),
+	(
+		ternary_assign,
+		"{{ x = \"a\" if c else \"b\" }}{{ x }}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"c": 1}"#).unwrap(), Ok("a")),
+			(Ctx::try_from(r#"{"c": 0}"#).unwrap(), Ok("b")),
+		]
+	),
+	(
+		ternary_in_collections,
+		"{{ [1 if a else 2, 3] }} {{ {\"k\": \"y\" if a else \"n\"} }}",
+		None,
+		None,
+		vec![(Ctx::try_from(r#"{"a": true}"#).unwrap(), Ok("[1, 3] {\"k\": \"y\"}"))]
+	),
+	(
+		ternary_lambda,
+		"{{ f = fn (x) => \"even\" if x % 2 == 0 else \"odd\" }}{{ f(2) }} {{ f(3) }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("even odd"))]
+	),
+	(
+		ternary_condition_in_if,
+		"{{if 1 if a else 0}}yes{{else}}no{{fi}}",
+		None,
+		None,
+		vec![
+			(Ctx::try_from(r#"{"a": true}"#).unwrap(), Ok("yes")),
+			(Ctx::try_from(r#"{"a": false}"#).unwrap(), Ok("no")),
+		]
+	),
+);
+
+#[test]
+fn ternary_missing_else() {
+	let mut osmia = crate::Osmia::default();
+	let err = osmia.run_code("{{ 1 if true }}").unwrap_err().to_string();
+	assert!(err.contains("Unclosed inline conditional"), "{}", err);
+}
+
+#[test]
+fn ternary_escaped() {
+	let mut osmia = crate::Osmia::try_from(r#"{"html": "<b>"}"#).unwrap()
+		.with_escape(crate::EscapeMode::Html);
+	assert_eq!(
+		osmia.run_code("{{ html?safe() if true else 1 }} {{ html if true else 1 }}").unwrap(),
+		"<b> &lt;b&gt;"
+	);
+}
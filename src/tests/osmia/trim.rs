@@ -0,0 +1,103 @@
+use super::*;
+
+macro_tests!(
+	test,
+	(
+		trim_01,
+		Some("a \n\t{{- 1 -}} \n b"),
+		Some(vec![
+			Token::new_raw("a"),
+			Token::StmtStart,
+			Token::new_number("1"),
+			Token::StmtEnd,
+			Token::new_raw("b"),
+			Token::Eof
+		]),
+		Some(Stmt::Block(vec![
+			Stmt::new_raw("a"),
+			Expr::Int(1).into(),
+			Stmt::new_raw("b"),
+		].into())),
+		Some(vec![(Ctx::new(), Ok("a1b"))])
+	),
+	(
+		trim_02,
+		Some("{{-2}} {{ 1 -}}\n\n{{- -2 }}"),
+		None,
+		None,
+		Some(vec![(Ctx::new(), Ok("-2 1-2"))])
+	),
+	(
+		trim_03,
+		Some("a\n\n  {{- # comment {{ 1 }} -}}\n\nb"),
+		Some(vec![
+			Token::new_raw("a"),
+			Token::StmtStart,
+			Token::Comment,
+			Token::new_raw(" comment {{ 1 }} "),
+			Token::StmtEnd,
+			Token::new_raw("b"),
+			Token::Eof
+		]),
+		None,
+		Some(vec![(Ctx::new(), Ok("ab"))])
+	),
+	(
+		trim_04,
+		Some("{{ {\"a\": 1} -}}  x"),
+		None,
+		None,
+		Some(vec![(Ctx::new(), Ok("{\"a\": 1}x"))])
+	),
+);
+
+macro_tests!(
+	test_code,
+	(
+		trim_inline_loop,
+		"<ul>\n  {{- for p in pages }}\n  <li>{{ p }}</li>\n  {{- done}}\n</ul>",
+		None,
+		None,
+		vec![(
+			Ctx::try_from(r#"{"pages": ["foo", "bar"]}"#).unwrap(),
+			Ok("<ul>\n  <li>foo</li>\n  <li>bar</li>\n</ul>")
+		)]
+	),
+	(
+		trim_inline_if,
+		"{{ x = 1 }}\nvalue: {{if x == 1 -}}\n  one\n{{- else -}}\n  other\n{{- fi -}}\n;",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("value: one;"))]
+	),
+	(
+		trim_gh_03,
+		"  {{- for p in pages -}}  {{p.name}},  {{- done -}}\n\n!",
+		None,
+		None,
+		vec![(
+			Ctx::try_from(r#"{"pages": [{"name": "foo"}, {"name": "bar"}]}"#).unwrap(),
+			Ok("foo,bar,!")
+		)]
+	),
+	(
+		trim_everything,
+		"\n\n {{- \"a\" -}} \n\n",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("a"))]
+	),
+	(
+		trim_custom_syntax_markers,
+		"{{ x = [1, 2] }}{{- x[0] -}} {{- x[1] }}",
+		None,
+		None,
+		vec![(Ctx::new(), Ok("12"))]
+	),
+);
+
+#[test]
+fn trim_custom_syntax() {
+	let mut osmia = crate::Osmia::default().with_syntax(crate::Syntax::new("<%", "%>"));
+	assert_eq!(osmia.run_code("a\n  <%- 1 -%>\n  b").unwrap(), "a1b");
+}
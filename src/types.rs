@@ -1,9 +1,9 @@
-use crate::model::lexer::Token;
+use crate::model::lexer::LocatedToken;
 use crate::model::stmt::Stmt;
 use std::cell::RefCell;
 
 /// The type of the lexer's output.
-pub type LexerCode = Vec<Token>;
+pub type LexerCode = Vec<LocatedToken>;
 
 /// ## Structure:
 #[doc = include_str!("../docs/parsing_structure.md")]
@@ -13,7 +13,7 @@ pub type ParserCode = Stmt;
 pub type OsmiaOutput = String;
 
 /// The type of the interpreter's error.
-pub type OsmiaError = String;
+pub use crate::model::error::OsmiaError;
 
 pub use crate::ctx::Ctx;
 pub type CtxRef<'a> = RefCell<&'a mut Ctx>;